@@ -4,6 +4,7 @@ use quick_xml::Reader;
 use std::time::Duration;
 
 use crate::app::NewsStory;
+use crate::archive::Archive;
 use crate::cache::Cache;
 
 fn create_http_client() -> Result<reqwest::blocking::Client> {
@@ -14,40 +15,54 @@ fn create_http_client() -> Result<reqwest::blocking::Client> {
         .context("Failed to create HTTP client")
 }
 
+/// Where a feed's stories came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedSource {
+    Cache,      // Cached and still fresh
+    Network,    // Just downloaded
+    Stale(u64), // Expired cache entry, because the network failed: seconds since it was fetched
+}
+
 pub fn fetch_stories(feed_url: &str) -> Result<Vec<NewsStory>> {
     fetch_stories_with_cache(feed_url, false)
 }
 
 pub fn fetch_stories_with_cache(feed_url: &str, force_offline: bool) -> Result<Vec<NewsStory>> {
-    let cache = Cache::new().ok();
+    let network = |url: &str| if force_offline {
+        Err(anyhow::anyhow!("Offline mode - skipping network fetch"))
+    } else {
+        fetch_from_network(url)
+    };
+    fetch_feed_from(Cache::new().ok().as_ref(), feed_url, network).map(|(stories, _)| stories)
+}
+
+/// Stories for a feed and where they came from: a fresh cache entry, the network, or an
+/// expired cache entry when the network is down
+pub fn fetch_feed(feed_url: &str) -> Result<(Vec<NewsStory>, FeedSource)> {
+    fetch_feed_from(Cache::new().ok().as_ref(), feed_url, fetch_from_network)
+}
 
+fn fetch_feed_from(
+    cache: Option<&Cache>,
+    feed_url: &str,
+    network: impl FnOnce(&str) -> Result<Vec<NewsStory>>,
+) -> Result<(Vec<NewsStory>, FeedSource)> {
     // Try to load from cache first (if not expired)
-    if let Some(ref cache) = cache {
-        if let Some(cached_stories) = cache.load_feed(feed_url) {
-            return Ok(cached_stories);
-        }
+    if let Some(cached_stories) = cache.and_then(|cache| cache.load_feed(feed_url)) {
+        return Ok((cached_stories, FeedSource::Cache));
     }
 
-    // Try to fetch from network
-    let stories_result = if !force_offline {
-        fetch_from_network(feed_url)
-    } else {
-        Err(anyhow::anyhow!("Offline mode - skipping network fetch"))
-    };
-
-    match stories_result {
+    match network(feed_url) {
         Ok(stories) => {
-            // Save to cache on successful fetch
-            if let Some(ref cache) = cache {
-                let _ = cache.save_feed(feed_url, &stories);
-            }
-            Ok(stories)
+            save_stories(cache, feed_url, &stories);
+            Ok((stories, FeedSource::Network))
         }
         Err(e) => {
             // Network failed, try to load from cache (even if expired)
-            if let Some(ref cache) = cache {
+            if let Some(cache) = cache {
                 if let Some(cached_stories) = cache.load_feed_offline(feed_url) {
-                    return Ok(cached_stories);
+                    let age = cache.get_feed_age(feed_url).unwrap_or_default();
+                    return Ok((cached_stories, FeedSource::Stale(age)));
                 }
             }
             Err(e)
@@ -55,6 +70,24 @@ pub fn fetch_stories_with_cache(feed_url: &str, force_offline: bool) -> Result<V
     }
 }
 
+/// Fetch from the network, bypassing any fresh cache entry (used by `bbcli sync`)
+pub fn refresh_stories(feed_url: &str) -> Result<Vec<NewsStory>> {
+    let stories = fetch_from_network(feed_url)?;
+    save_stories(Cache::new().ok().as_ref(), feed_url, &stories);
+    Ok(stories)
+}
+
+fn save_stories(cache: Option<&Cache>, feed_url: &str, stories: &[NewsStory]) {
+    // Save to cache on successful fetch
+    if let Some(cache) = cache {
+        let _ = cache.save_feed(feed_url, stories);
+    }
+    // Keep every story ever seen in the local archive
+    if let Ok(mut archive) = Archive::open() {
+        let _ = archive.record_stories(feed_url, stories);
+    }
+}
+
 fn fetch_from_network(feed_url: &str) -> Result<Vec<NewsStory>> {
     let client = create_http_client()?;
 
@@ -120,30 +153,26 @@ fn parse_rss(xml_content: &str) -> Result<Vec<NewsStory>> {
                     current_tag = tag_name;
                 }
             }
-            Ok(Event::Text(e)) => {
-                if in_item {
-                    let text = e.unescape().unwrap_or_default().to_string();
-                    match current_tag.as_str() {
-                        "title" => current_story.title = text,
-                        "description" => current_story.description = text,
-                        "link" => current_story.link = text,
-                        "pubDate" => current_story.pub_date = format_date(&text),
-                        "category" => current_story.category = text,
-                        _ => {}
-                    }
+            Ok(Event::Text(e)) if in_item => {
+                let text = e.unescape().unwrap_or_default().to_string();
+                match current_tag.as_str() {
+                    "title" => current_story.title = text,
+                    "description" => current_story.description = text,
+                    "link" => current_story.link = text,
+                    "pubDate" => current_story.pub_date = format_date(&text),
+                    "category" => current_story.category = text,
+                    _ => {}
                 }
             }
-            Ok(Event::CData(e)) => {
-                if in_item {
-                    let text = String::from_utf8_lossy(&e.into_inner()).to_string();
-                    match current_tag.as_str() {
-                        "title" => current_story.title = text,
-                        "description" => current_story.description = text,
-                        "link" => current_story.link = text,
-                        "pubDate" => current_story.pub_date = format_date(&text),
-                        "category" => current_story.category = text,
-                        _ => {}
-                    }
+            Ok(Event::CData(e)) if in_item => {
+                let text = String::from_utf8_lossy(&e.into_inner()).to_string();
+                match current_tag.as_str() {
+                    "title" => current_story.title = text,
+                    "description" => current_story.description = text,
+                    "link" => current_story.link = text,
+                    "pubDate" => current_story.pub_date = format_date(&text),
+                    "category" => current_story.category = text,
+                    _ => {}
                 }
             }
             Ok(Event::Empty(e)) => {
@@ -197,3 +226,40 @@ fn format_date(date_str: &str) -> String {
         date_str.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reports_where_stories_came_from() {
+        let dir = std::env::temp_dir().join(format!("bbcli-api-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = Cache::with_dir(dir.clone()).unwrap();
+        let url = "https://feeds.bbci.co.uk/news/world/rss.xml";
+        let story = NewsStory {
+            title: String::from("Cached story"),
+            description: String::new(),
+            link: String::from("https://www.bbc.co.uk/news/1"),
+            pub_date: String::from("2025-01-01 12:00"),
+            category: String::from("News"),
+            image_url: None,
+        };
+        let offline = |_: &str| Err(anyhow::anyhow!("network is down"));
+
+        // Nothing cached and no network
+        assert!(fetch_feed_from(Some(&cache), url, offline).is_err());
+
+        // An expired copy while offline is stale, with how old it is
+        cache.save_expired_feed(url, std::slice::from_ref(&story), 600).unwrap();
+        let (stories, source) = fetch_feed_from(Some(&cache), url, offline).unwrap();
+        assert_eq!(stories[0].title, "Cached story");
+        assert!(matches!(source, FeedSource::Stale(age) if age >= 600), "{:?}", source);
+
+        // A fresh copy doesn't touch the network
+        cache.save_feed(url, std::slice::from_ref(&story)).unwrap();
+        assert_eq!(fetch_feed_from(Some(&cache), url, |_: &str| unreachable!()).unwrap().1, FeedSource::Cache);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
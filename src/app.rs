@@ -1,7 +1,38 @@
-use crate::feeds::{Feed, get_default_feed};
-use crate::theme::Theme;
+use crate::bookmarks::Bookmarks;
+use crate::color_depth::ColorDepth;
+use crate::feeds::{Feed, SAVED_FEED_URL, get_default_feed};
+use crate::config::{LayoutConfig, ReaderConfig};
+use crate::input::InputFilter;
+use crate::keymap::{KeyChord, KeyMode, Keymap};
+use crate::palette::Palette;
+use crate::read_state::ReadState;
+use crate::reader::{self, ArticleLine};
+use crate::reading_positions::ReadingPositions;
+use crate::tabs::Tab;
+use crate::theme::{Theme, ThemeName};
 use std::time::{Instant, Duration};
 use std::collections::HashMap;
+use ratatui::layout::Size;
+
+// Auto-refresh every 5 minutes (300 seconds)
+pub const AUTO_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
+
+// Largest count prefix; longer counts are capped rather than overflowing
+pub const MAX_COUNT: usize = 9999;
+
+// Lines shown above an article search match
+const ARTICLE_MATCH_CONTEXT: usize = 2;
+
+// Percent of the screen each resize key press moves the preview edge
+const PREVIEW_RESIZE_STEP: isize = 5;
+
+// How long a config warning replaces the ticker (approximately 8 seconds at 100ms polling)
+const CONFIG_WARNING_TICKS: u32 = 80;
+
+// Keep both sides of a resizable split usable
+fn clamp_percent(percent: isize) -> u16 {
+    percent.clamp(10, 90) as u16
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppMode {
@@ -9,16 +40,26 @@ pub enum AppMode {
     FeedMenu,
     Preview,
     Help,
+    Search,
+    ArchiveSearch,
+    ArticleSearch,
+    Palette,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum SortOrder {
+    #[default]
+    #[serde(rename = "default")]
     Default,         // RSS feed order (as received)
+    #[serde(rename = "newest")]
     DateNewest,      // Newest first
+    #[serde(rename = "oldest")]
     DateOldest,      // Oldest first
 }
 
 impl SortOrder {
+    pub const ALL: [SortOrder; 3] = [SortOrder::Default, SortOrder::DateNewest, SortOrder::DateOldest];
+
     pub fn next(&self) -> Self {
         match self {
             SortOrder::Default => SortOrder::DateNewest,
@@ -36,8 +77,10 @@ impl SortOrder {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ImageProtocol {
+    #[default]
     Auto,       // Automatically detect best protocol
     Halfblocks, // Unicode half blocks (widely compatible)
     Sixel,      // High quality Sixel graphics
@@ -45,6 +88,9 @@ pub enum ImageProtocol {
 }
 
 impl ImageProtocol {
+    pub const ALL: [ImageProtocol; 4] =
+        [ImageProtocol::Auto, ImageProtocol::Halfblocks, ImageProtocol::Sixel, ImageProtocol::Kitty];
+
     pub fn next(&self) -> Self {
         match self {
             ImageProtocol::Auto => ImageProtocol::Halfblocks,
@@ -64,6 +110,32 @@ impl ImageProtocol {
     }
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutMode {
+    #[default]
+    Compact,    // Story list, with the preview beside it when enabled
+    Panes,      // Feed sidebar, story list and reading pane
+}
+
+/// Where the preview, or the reading pane, sits relative to the story list
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PreviewPosition {
+    #[default]
+    Right,
+    Bottom,
+}
+
+/// Which pane has the keyboard in the three-pane layout
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Pane {
+    Sidebar,
+    #[default]
+    Stories,
+    Reader,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct NewsStory {
     pub title: String,
@@ -82,6 +154,7 @@ pub struct App {
     pub is_loading: bool,
     pub is_refreshing: bool,               // Track if currently refreshing data
     pub is_offline: bool,                  // Track offline mode
+    pub offline_since: Option<chrono::DateTime<chrono::Local>>, // When the cached (offline) feed was fetched
     pub error_message: Option<String>,
     pub ticker_index: usize,
     pub ticker_counter: u32,
@@ -92,18 +165,38 @@ pub struct App {
     pub humanize_dates: bool,
     pub image_protocol: ImageProtocol,
     pub theme: Theme,                      // Current theme
+    pub themes: Vec<Theme>,                // Every theme T cycles through, mapped to color_depth
+    pub color_depth: ColorDepth,           // Colours the terminal can show
     pub show_full_article: bool,           // Toggle between preview and full article view
-    pub article_scroll_offset: usize,      // Scroll position in article view
+    pub article_scroll_offset: usize,      // Scroll position in article view, in wrapped lines
+    pub article_view: Size,                // Text area of the article view, for wrapping and page moves
+    pub article_query: String,             // Search within the open article
+    pub article_match: Option<usize>,      // Current match, by position among the lines that match
+    pub reading_positions: ReadingPositions, // Where reading stopped in each article
     pub is_fetching_article: bool,         // Loading state for article fetching
     pub sort_order: SortOrder,             // Current sort order
     pub last_refresh_time: Instant,        // Track last refresh for auto-refresh
-    article_cache: HashMap<String, String>, // Cache fetched articles by URL
-    last_opened_index: Option<usize>,      // Track last opened article index to prevent repeated opens
-    last_open_time: Option<Instant>,       // Track last open time for cooldown
-    last_selection_change_time: Instant,   // Track when selection last changed
-    scroll_count: u32,                     // Count rapid scroll events (for storm detection)
-    last_scroll_time: Instant,             // Track last scroll event time
-    scroll_pause_until: Option<Instant>,   // When to resume scrolling after storm
+    pub search_query: String,              // Active search filter (empty = no filter)
+    pub search_matches: Vec<usize>,        // Indices of stories matching the search filter
+    pub read_state: ReadState,             // Persistent read/unread tracking
+    pub feed_unread_counts: HashMap<String, usize>, // Unread counts per feed URL, as of its last fetch
+    pub bookmarks: Bookmarks,              // Saved stories (read-later list)
+    pub archive_query: String,             // Query for the archive search results feed
+    pub config_message: Option<String>,    // Config error or warning shown in the footer
+    pub config_warning_ticks: u32,         // Ticks left before a warning clears (0 = stays)
+    pub keymap: Keymap,                    // Key bindings for every mode
+    pub pending_keys: Vec<KeyChord>,       // Keys typed so far in a multi-key sequence
+    pub pending_count: Option<usize>,      // Count typed before a move, like the 5 in "5j"
+    pub list_height: u16,                  // Rows the story list takes up, for page moves
+    pub input: InputFilter,                // Key repeat and burst detection
+    pub confirm_open: bool,                // Waiting for y before opening the selected story
+    pub palette: Option<Palette>,          // Command palette, while it's open
+    pub layout: LayoutConfig,              // Compact or three-pane, and the pane sizes
+    pub reader: ReaderConfig,              // Measure, justification and spacing of the article text
+    pub focus: Pane,                       // Focused pane in the three-pane layout
+    pub tabs: Vec<Tab>,                    // Open feeds; the active one's state is in the fields above
+    pub active_tab: usize,                 // Position of the current feed in the tabs
+    pub(crate) article_cache: HashMap<String, String>, // Cache fetched articles by URL
 }
 
 impl App {
@@ -116,6 +209,7 @@ impl App {
             is_loading: true,
             is_refreshing: false,                  // Not refreshing initially
             is_offline: false,                     // Start in online mode
+            offline_since: None,                   // No offline edition yet
             error_message: None,
             ticker_index: 0,
             ticker_counter: 0,
@@ -126,157 +220,104 @@ impl App {
             humanize_dates: true,  // Default to humanized dates
             image_protocol: ImageProtocol::Auto,  // Auto-detect best protocol
             theme,                                 // Theme from config
+            themes: crate::theme::load_themes(&Default::default(), None).0, // Built-ins until config is loaded
+            color_depth: ColorDepth::TrueColor,
             show_full_article: false,              // Start in preview mode
             article_scroll_offset: 0,              // Start at top of article
+            article_view: Size::default(),         // Set from the terminal size before each key
+            article_query: String::new(),          // No article search
+            article_match: None,
+            reading_positions: ReadingPositions::default(), // In-memory until loaded from disk
             is_fetching_article: false,            // Not fetching initially
             sort_order: SortOrder::Default,        // Default RSS order
             last_refresh_time: Instant::now(),     // Initialize to now
+            search_query: String::new(),           // No search filter
+            search_matches: Vec::new(),            // No matches
+            read_state: ReadState::default(),      // In-memory until loaded from disk
+            feed_unread_counts: HashMap::new(),    // Filled in as feeds are fetched
+            bookmarks: Bookmarks::default(),       // In-memory until loaded from disk
+            archive_query: String::new(),          // No archive search yet
+            config_message: None,                  // Config is checked at startup and on change
+            config_warning_ticks: 0,               // Errors stay until the file is fixed
+            keymap: Keymap::default(),             // Replaced by the configured keymap
+            pending_keys: Vec::new(),              // No sequence in progress
+            pending_count: None,                   // No count typed
+            list_height: 0,                        // Set from the terminal size before each key
+            input: InputFilter::default(),         // Replaced by the configured thresholds
+            confirm_open: false,                   // Nothing to confirm
+            palette: None,                         // Opened with : or ctrl-p
+            layout: LayoutConfig::default(),       // Replaced by the configured layout
+            reader: ReaderConfig::default(),       // Replaced by the configured reading options
+            focus: Pane::Stories,                  // Keys go to the story list
+            tabs: vec![Tab::new(get_default_feed())], // Just the current feed
+            active_tab: 0,
             article_cache: HashMap::new(),         // Empty cache
-            last_opened_index: None,               // No article opened yet
-            last_open_time: None,                  // No article opened yet
-            last_selection_change_time: Instant::now(),  // Initialize to now
-            scroll_count: 0,                       // No scrolls yet
-            last_scroll_time: Instant::now(),      // Initialize to now
-            scroll_pause_until: None,              // Not paused
         }
     }
 
-    // Scroll storm detection: prevents catastrophic event buffering in Ghostty
-    // Returns true if scrolling should be allowed, false if paused
-    fn check_scroll_storm(&mut self) -> bool {
-        // Only enable storm detection when preview is OPEN
-        // (When preview is closed, scroll keys work normally without blocking)
-        if !self.show_preview {
-            return true; // Preview closed, allow all scrolling
-        }
-
-        // Check if currently paused
-        if let Some(pause_until) = self.scroll_pause_until {
-            if Instant::now() < pause_until {
-                return false; // Still paused, ignore scroll
-            } else {
-                // Pause expired, resume scrolling
-                self.scroll_pause_until = None;
-                self.scroll_count = 0;
-            }
-        }
-
-        // Check if this is part of a scroll storm
-        let now = Instant::now();
-        let time_since_last_scroll = now.duration_since(self.last_scroll_time).as_secs_f32();
-
-        if time_since_last_scroll > 0.5 {
-            // Been a while since last scroll, reset counter
-            self.scroll_count = 0;
-        }
-
-        // Increment scroll count
-        self.scroll_count += 1;
-        self.last_scroll_time = now;
-
-        // Check if storm detected (more than 10 scrolls in 0.5 seconds)
-        if self.scroll_count > 10 && time_since_last_scroll < 0.5 {
-            // SCROLL STORM DETECTED! Pause scrolling for 1 second
-            self.scroll_pause_until = Some(now + std::time::Duration::from_secs(1));
-            self.scroll_count = 0;
-            return false; // Ignore this scroll
-        }
+    pub fn next(&mut self) {
+        self.move_selection(1);
+    }
 
-        true // Allow scroll
+    pub fn previous(&mut self) {
+        self.move_selection(-1);
     }
 
-    pub fn next(&mut self) {
-        // Check for scroll storm - pause scrolling if detected
-        if !self.check_scroll_storm() {
-            return; // Scroll storm detected, ignoring this scroll event
+    /// Move the selection by a number of stories, stopping at either end
+    pub fn move_selection(&mut self, delta: isize) {
+        if !self.stories.is_empty() {
+            let target = self.selected.saturating_add_signed(delta).min(self.stories.len() - 1);
+            self.set_selected(target);
         }
+    }
 
+    /// Select a story by the number shown in the list (1-based), clamped to the list
+    pub fn go_to_story(&mut self, number: usize) {
         if !self.stories.is_empty() {
-            self.selected = (self.selected + 1).min(self.stories.len() - 1);
-            // Clear last opened index when selection changes - allows opening new article
-            self.last_opened_index = None;
-            // Record when selection changed - prevents immediate opens
-            self.last_selection_change_time = Instant::now();
+            self.set_selected(number.clamp(1, self.stories.len()) - 1);
         }
     }
 
-    pub fn previous(&mut self) {
-        // Check for scroll storm - pause scrolling if detected
-        if !self.check_scroll_storm() {
-            return; // Scroll storm detected, ignoring this scroll event
-        }
+    /// Stories that fit on screen (each takes a title and a metadata line)
+    pub fn list_page_size(&self) -> usize {
+        (self.list_height as usize / 2).max(1)
+    }
 
-        if self.selected > 0 {
-            self.selected -= 1;
-            // Clear last opened index when selection changes - allows opening new article
-            self.last_opened_index = None;
-            // Record when selection changed - prevents immediate opens
-            self.last_selection_change_time = Instant::now();
-        }
+    /// Article lines that fit on screen (inside the border)
+    pub fn article_page_size(&self) -> usize {
+        (self.article_view.height as usize).max(1)
     }
 
-    pub fn scroll_to_bottom(&mut self) {
-        // Check for scroll storm - pause scrolling if detected
-        if !self.check_scroll_storm() {
-            return; // Scroll storm detected, ignoring this scroll event
-        }
+    /// Add a digit to the count prefix
+    pub fn push_count_digit(&mut self, digit: usize) {
+        let count = self.pending_count.unwrap_or(0);
+        self.pending_count = Some((count * 10 + digit).min(MAX_COUNT));
+    }
 
+    pub fn scroll_to_bottom(&mut self) {
         if !self.stories.is_empty() {
             self.selected = self.stories.len() - 1;
-            // Clear last opened index when selection changes - allows opening new article
-            self.last_opened_index = None;
-            // Record when selection changed - prevents immediate opens
-            self.last_selection_change_time = Instant::now();
         }
     }
 
     pub fn scroll_to_top(&mut self) {
-        // Check for scroll storm - pause scrolling if detected
-        if !self.check_scroll_storm() {
-            return; // Scroll storm detected, ignoring this scroll event
-        }
-
         self.selected = 0;
-        // Clear last opened index when selection changes - allows opening new article
-        self.last_opened_index = None;
-        // Record when selection changed - prevents immediate opens
-        self.last_selection_change_time = Instant::now();
     }
 
     pub fn open_selected(&mut self) -> anyhow::Result<()> {
-        // AGGRESSIVE PROTECTION against Ghostty event buffering catastrophe:
-
-        // 1. SELECTION STABILITY: Prevent opening if selection changed recently
-        //    (Requires 3 seconds on same article before opening)
-        if self.last_selection_change_time.elapsed().as_secs_f32() < 3.0 {
-            return Ok(()); // Selection changed too recently, ignore
-        }
-
-        // 2. TIME-BASED: Prevent opening ANY article within 5 seconds of last open
-        //    (Prevents rapid-fire opens even when scrolling to different articles)
-        if let Some(last_time) = self.last_open_time {
-            if last_time.elapsed().as_secs_f32() < 5.0 {
-                return Ok(()); // Still in cooldown, ignore
-            }
-        }
-
-        // 3. INDEX-BASED: Prevent opening same article multiple times
-        //    (Additional protection against repeated opens)
-        if let Some(last_idx) = self.last_opened_index {
-            if last_idx == self.selected {
-                return Ok(()); // Same article, ignore
-            }
-        }
-
-        // All checks passed, open the article
         if let Some(story) = self.stories.get(self.selected) {
             webbrowser::open(&story.link)?;
-            self.last_opened_index = Some(self.selected);
-            self.last_open_time = Some(Instant::now());
+            self.read_state.mark_read(&story.link);
         }
         Ok(())
     }
 
+    /// Ask before opening, for opens that arrive in a burst of input.
+    /// Held or buffered keys could otherwise open whatever the selection landed on.
+    pub fn request_open_confirmation(&mut self) {
+        self.confirm_open = !self.stories.is_empty();
+    }
+
     pub fn open_selected_new_tab(&mut self) -> anyhow::Result<()> {
         // Most browsers will open in a new tab by default
         self.open_selected()
@@ -288,6 +329,7 @@ impl App {
 
     pub fn update_stories(&mut self, stories: Vec<NewsStory>) {
         self.stories = stories;
+        self.record_current_unread_count();
         self.is_loading = false;
         self.is_refreshing = false;
         // Check selection bounds before sorting
@@ -299,6 +341,8 @@ impl App {
         if self.sort_order != crate::app::SortOrder::Default {
             self.apply_sort();
         }
+        // Keep search filter active across refreshes
+        self.update_search_matches();
     }
 
     pub fn update_ticker_stories(&mut self, stories: Vec<NewsStory>) {
@@ -316,15 +360,53 @@ impl App {
         self.error_message = None;
     }
 
+    /// Show the first config warning in the footer for a few seconds, or clear it if there
+    /// are none. The settings still applied, so the ticker comes back after the next key.
+    pub fn set_config_warnings(&mut self, warnings: &[String]) {
+        self.config_message = warnings.first().map(|warning| {
+            let more = match warnings.len() {
+                1 => String::new(),
+                n => format!(" (+{} more)", n - 1),
+            };
+            format!("Config: {}{}. Run `bbcli config check`", warning, more)
+        });
+        self.config_warning_ticks = if self.config_message.is_some() { CONFIG_WARNING_TICKS } else { 0 };
+    }
+
+    /// Clear a config warning early. Errors stay until the file is fixed.
+    pub fn dismiss_config_warning(&mut self) {
+        if self.config_warning_ticks > 0 {
+            self.config_warning_ticks = 0;
+            self.config_message = None;
+        }
+    }
+
+    /// Show a config error in the footer, flattened to one line
+    pub fn set_config_error(&mut self, error: &anyhow::Error) {
+        // Drop the source snippet lines ("2 | quit = ...", "  |   ^^^") from toml errors
+        let message = format!("{:#}", error)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.trim_start_matches(|c: char| c.is_ascii_digit()).trim_start().starts_with('|'))
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.config_message = Some(format!("Config not applied: {}", message));
+        self.config_warning_ticks = 0;
+    }
+
     pub fn tick(&mut self) -> bool {
         self.ticker_counter += 1;
+        if self.config_warning_ticks == 1 {
+            self.dismiss_config_warning();
+        }
+        self.config_warning_ticks = self.config_warning_ticks.saturating_sub(1);
         // Rotate ticker every 100 ticks (approximately 10 seconds at 100ms polling)
         if self.ticker_counter >= 100 {
             self.ticker_counter = 0;
             self.rotate_ticker();
         }
         // Return true every 10 ticks (approximately 1 second) to trigger clock update
-        self.ticker_counter % 10 == 0
+        self.ticker_counter.is_multiple_of(10)
     }
 
     fn rotate_ticker(&mut self) {
@@ -341,10 +423,128 @@ impl App {
         self.mode = if self.mode == AppMode::FeedMenu {
             AppMode::Normal
         } else {
+            self.update_feed_unread_counts();
             AppMode::FeedMenu
         };
     }
 
+    /// Remember how many of a feed's stories are unread, for the feed menu and sidebar
+    pub fn record_unread_count(&mut self, url: &str, stories: &[NewsStory]) {
+        self.feed_unread_counts.insert(url.to_string(), self.read_state.unread_count(stories));
+    }
+
+    fn record_current_unread_count(&mut self) {
+        let count = self.unread_count();
+        self.feed_unread_counts.insert(self.current_feed.url.clone(), count);
+    }
+
+    // Recount the feeds whose stories are in memory; the rest keep the count from their last fetch
+    fn update_feed_unread_counts(&mut self) {
+        self.record_current_unread_count();
+        let saved = self.read_state.unread_count(&self.bookmarks.stories());
+        self.feed_unread_counts.insert(SAVED_FEED_URL.to_string(), saved);
+        for (i, tab) in self.tabs.iter().enumerate() {
+            if i != self.active_tab && !tab.stories.is_empty() {
+                self.feed_unread_counts.insert(tab.feed.url.clone(), self.read_state.unread_count(&tab.stories));
+            }
+        }
+    }
+
+    // Read state methods
+    pub fn toggle_read(&mut self) {
+        if let Some(story) = self.stories.get(self.selected) {
+            self.read_state.toggle(&story.link);
+        }
+    }
+
+    pub fn mark_all_read(&mut self) {
+        self.read_state.mark_all_read(&self.stories);
+    }
+
+    // Bookmark methods
+    pub fn toggle_bookmark(&mut self) {
+        let Some(story) = self.stories.get(self.selected).cloned() else {
+            return;
+        };
+
+        if self.bookmarks.contains(&story.link) {
+            if let Err(e) = self.bookmarks.remove(&story.link) {
+                self.set_error(format!("Failed to remove bookmark: {}", e));
+            }
+            // Keep the Saved feed in sync with the bookmark list
+            if self.current_feed.is_saved() {
+                self.update_stories(self.bookmarks.stories());
+            }
+            return;
+        }
+
+        // Store the article body with the bookmark so it can be read offline. One that isn't
+        // downloaded yet is filled in when the article is opened or by `bbcli sync`, so
+        // saving never waits on the network.
+        let article = self.article_cache.get(&story.link).cloned();
+
+        let feed_name = self.current_feed.name.clone();
+        if let Err(e) = self.bookmarks.add(&story, &feed_name, article) {
+            self.set_error(format!("Failed to save bookmark: {}", e));
+        }
+    }
+
+    pub fn is_bookmarked(&self, index: usize) -> bool {
+        self.stories.get(index)
+            .map(|story| self.bookmarks.contains(&story.link))
+            .unwrap_or(false)
+    }
+
+    pub fn is_read(&self, index: usize) -> bool {
+        self.stories.get(index)
+            .map(|story| self.read_state.is_read(&story.link))
+            .unwrap_or(false)
+    }
+
+    pub fn unread_count(&self) -> usize {
+        self.read_state.unread_count(&self.stories)
+    }
+
+    /// Apply the display defaults from config
+    pub fn apply_display_config(&mut self, display: &crate::config::DisplayConfig) {
+        self.humanize_dates = display.humanize_dates;
+        self.image_protocol = display.image_protocol.clone();
+        self.show_preview = display.preview;
+        if self.sort_order != display.sort {
+            self.sort_order = display.sort.clone();
+            self.apply_sort();
+        }
+    }
+
+    /// Give the preview (or the reading pane) more of the screen, or less with a negative step
+    pub fn resize_preview(&mut self, steps: isize) {
+        let delta = steps * PREVIEW_RESIZE_STEP;
+        if self.is_panes() {
+            self.layout.list_percent = clamp_percent(self.layout.list_percent as isize - delta);
+        } else {
+            self.layout.preview_percent = clamp_percent(self.layout.preview_percent as isize + delta);
+            // Resizing a hidden preview would do nothing visible
+            self.show_preview = true;
+        }
+    }
+
+    pub fn toggle_preview_position(&mut self) {
+        self.layout.preview_position = match self.layout.preview_position {
+            PreviewPosition::Right => PreviewPosition::Bottom,
+            PreviewPosition::Bottom => PreviewPosition::Right,
+        };
+    }
+
+    /// Apply the layout from config, switching layout if its mode differs from the current one
+    pub fn apply_layout_config(&mut self, layout: &LayoutConfig) {
+        let mode = self.layout.mode;
+        self.layout = layout.clone();
+        if layout.mode != mode {
+            self.layout.mode = mode;
+            self.toggle_layout();
+        }
+    }
+
     pub fn toggle_preview(&mut self) {
         self.show_preview = !self.show_preview;
     }
@@ -370,17 +570,93 @@ impl App {
     }
 
     pub fn select_feed(&mut self, feed: Feed) {
+        // Keep the count of the feed being left, with any stories read since it was fetched
+        self.record_current_unread_count();
         self.current_feed = feed;
         self.mode = AppMode::Normal;
         self.is_loading = true;
         self.selected = 0;
     }
 
+    /// Open a feed in a new tab after the current one, or switch to it if it's already open.
+    /// Returns true when the feed needs fetching.
+    pub fn open_tab(&mut self, feed: Feed) -> bool {
+        self.mode = AppMode::Normal;
+        self.save_tab();
+        if let Some(index) = self.tabs.iter().position(|tab| tab.feed.url == feed.url) {
+            return self.switch_tab(index);
+        }
+        self.active_tab += 1;
+        self.tabs.insert(self.active_tab, Tab::new(feed));
+        self.restore_tab()
+    }
+
+    /// Close the current tab, unless it's the last one. Returns true when the tab
+    /// switched to needs fetching.
+    pub fn close_tab(&mut self) -> bool {
+        if self.tabs.len() <= 1 {
+            return false;
+        }
+        self.tabs.remove(self.active_tab);
+        self.active_tab = self.active_tab.min(self.tabs.len() - 1);
+        self.restore_tab()
+    }
+
+    /// Switch to the tab at a position. Returns true when it needs fetching.
+    pub fn switch_tab(&mut self, index: usize) -> bool {
+        if index >= self.tabs.len() || index == self.active_tab {
+            return false;
+        }
+        self.save_tab();
+        self.active_tab = index;
+        self.restore_tab()
+    }
+
+    /// Move through the tabs by a number of steps in either direction, wrapping at the ends
+    pub fn cycle_tab(&mut self, steps: isize) -> bool {
+        let count = self.tabs.len() as isize;
+        self.switch_tab((self.active_tab as isize + steps).rem_euclid(count) as usize)
+    }
+
+    /// Names of the open feeds, in tab order
+    pub fn tab_names(&self) -> Vec<&str> {
+        self.tabs.iter()
+            .enumerate()
+            .map(|(i, tab)| if i == self.active_tab { self.current_feed.name.as_str() } else { tab.feed.name.as_str() })
+            .collect()
+    }
+
+    /// Background tabs due for a refresh
+    pub fn tabs_to_refresh(&self) -> Vec<usize> {
+        (0..self.tabs.len()).filter(|&i| i != self.active_tab && self.tabs[i].needs_refresh()).collect()
+    }
+
+    fn save_tab(&mut self) {
+        self.tabs[self.active_tab] = Tab::capture(self);
+    }
+
+    fn restore_tab(&mut self) -> bool {
+        let tab = self.tabs[self.active_tab].clone();
+        // Saved stories may have changed while the tab was in the background
+        let needs_fetch = !tab.loaded || tab.feed.is_saved();
+        tab.restore(self);
+        self.mode = AppMode::Normal;
+        self.pending_count = None;
+        needs_fetch
+    }
+
     // Article viewing methods
     pub fn fetch_and_show_article(&mut self) {
         if let Some(story) = self.stories.get(self.selected) {
             let url = &story.link;
 
+            // Saved stories carry their own article body (works offline)
+            if !self.article_cache.contains_key(url) {
+                if let Some(content) = self.bookmarks.article(url) {
+                    self.article_cache.insert(url.clone(), content.clone());
+                }
+            }
+
             // Check cache first
             if !self.article_cache.contains_key(url) {
                 // Not in cache, fetch it
@@ -391,8 +667,7 @@ impl App {
                     Ok(content) => {
                         self.article_cache.insert(url.clone(), content);
                         self.is_fetching_article = false;
-                        self.show_full_article = true;
-                        self.article_scroll_offset = 0;
+                        self.show_article();
                     }
                     Err(e) => {
                         self.is_fetching_article = false;
@@ -401,29 +676,189 @@ impl App {
                 }
             } else {
                 // Already cached, show it
-                self.show_full_article = true;
-                self.article_scroll_offset = 0;
+                self.show_article();
             }
         }
     }
 
+    // Open the selected story's cached article where reading last stopped
+    fn show_article(&mut self) {
+        let Some(link) = self.stories.get(self.selected).map(|story| story.link.clone()) else {
+            return;
+        };
+        self.read_state.mark_read(&link);
+        // A story saved before its article was downloaded keeps it now
+        if let Some(content) = self.article_cache.get(&link) {
+            let _ = self.bookmarks.fill_article(&link, content);
+        }
+        self.show_full_article = true;
+        self.focus = Pane::Reader;
+        self.clear_article_search();
+        self.article_scroll_offset = 0;
+        if let Some(offset) = self.reading_positions.get(&link) {
+            let line = reader::line_at(&self.article_lines(), offset);
+            self.scroll_article_to(line);
+        }
+    }
+
+    // Leave the article, remembering the position for next time
+    fn close_article(&mut self) {
+        self.remember_article_position();
+        self.show_full_article = false;
+        self.article_scroll_offset = 0;
+        self.clear_article_search();
+    }
+
+    /// Save how far through the open article reading got. Articles that weren't started or were
+    /// read to the end open at the top next time.
+    pub fn remember_article_position(&mut self) {
+        if !self.show_full_article {
+            return;
+        }
+        let Some(link) = self.stories.get(self.selected).map(|story| story.link.clone()) else {
+            return;
+        };
+        let lines = self.article_lines();
+        let scroll = self.article_scroll_offset;
+        let finished = scroll >= reader::max_scroll(lines.len(), self.article_page_size());
+        let offset = lines.get(scroll).map(|line| line.start).filter(|_| scroll > 0 && !finished);
+        self.reading_positions.set(&link, offset);
+    }
+
     pub fn toggle_article_view(&mut self) {
         if self.show_full_article {
             // Return to preview
-            self.show_full_article = false;
-            self.article_scroll_offset = 0;
+            self.close_article();
         }
+        self.focus = Pane::Stories;
     }
 
-    pub fn scroll_article_up(&mut self) {
-        if self.article_scroll_offset > 0 {
-            self.article_scroll_offset = self.article_scroll_offset.saturating_sub(1);
+    pub fn is_panes(&self) -> bool {
+        self.layout.mode == LayoutMode::Panes
+    }
+
+    /// Whether the full article is on screen and has the keyboard
+    pub fn is_reading_article(&self) -> bool {
+        self.show_full_article && (!self.is_panes() || self.focus == Pane::Reader)
+    }
+
+    pub fn is_sidebar_focused(&self) -> bool {
+        self.is_panes() && self.focus == Pane::Sidebar && self.mode == AppMode::Normal
+    }
+
+    pub fn toggle_layout(&mut self) {
+        self.layout.mode = match self.layout.mode {
+            LayoutMode::Compact => LayoutMode::Panes,
+            LayoutMode::Panes => LayoutMode::Compact,
+        };
+        self.focus_pane(if self.show_full_article { Pane::Reader } else { Pane::Stories });
+        if self.is_panes() {
+            self.update_feed_unread_counts();
         }
     }
 
-    pub fn scroll_article_down(&mut self) {
-        // Scroll down (limit will be checked during rendering)
-        self.article_scroll_offset += 1;
+    /// Move focus to a pane. Leaving the reading pane closes the article, so the
+    /// pane follows the selection again.
+    pub fn focus_pane(&mut self, pane: Pane) {
+        if !self.is_panes() {
+            return;
+        }
+        if pane == Pane::Sidebar {
+            let feeds = crate::feeds::get_menu_feeds();
+            self.feed_menu_selected = feeds.iter().position(|feed| feed.url == self.current_feed.url).unwrap_or(0);
+        }
+        if self.focus == Pane::Reader && pane != Pane::Reader {
+            self.close_article();
+        }
+        self.focus = pane;
+    }
+
+    pub fn focus_left(&mut self) {
+        match self.focus {
+            Pane::Reader => self.focus_pane(Pane::Stories),
+            _ => self.focus_pane(Pane::Sidebar),
+        }
+    }
+
+    pub fn focus_right(&mut self) {
+        match self.focus {
+            Pane::Sidebar => self.focus_pane(Pane::Stories),
+            // The reading pane shows the preview until there's an article to scroll
+            _ if self.is_panes() => self.fetch_and_show_article(),
+            _ => {}
+        }
+    }
+
+    /// Scroll the article by a number of lines in either direction, stopping at either end
+    pub fn scroll_article_by(&mut self, delta: isize) {
+        self.scroll_article_to(self.article_scroll_offset.saturating_add_signed(delta));
+    }
+
+    /// Scroll so a line is at the top, or as near as the end of the article allows
+    pub fn scroll_article_to(&mut self, line: usize) {
+        let max = reader::max_scroll(self.article_lines().len(), self.article_page_size());
+        self.article_scroll_offset = line.min(max);
+    }
+
+    /// The open article wrapped to the width of the article view
+    pub fn article_lines(&self) -> Vec<ArticleLine> {
+        self.get_current_article_text()
+            .map(|text| reader::wrap(text, self.article_view.width as usize, &self.reader))
+            .unwrap_or_default()
+    }
+
+    pub fn start_article_search(&mut self) {
+        self.clear_article_search();
+        self.mode = AppMode::ArticleSearch;
+    }
+
+    pub fn article_search_push(&mut self, c: char) {
+        self.article_query.push(c);
+        self.find_article_match();
+    }
+
+    pub fn article_search_pop(&mut self) {
+        self.article_query.pop();
+        self.find_article_match();
+    }
+
+    /// Keep the matches highlighted and return to scrolling
+    pub fn confirm_article_search(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    pub fn clear_article_search(&mut self) {
+        self.article_query.clear();
+        self.article_match = None;
+        if self.mode == AppMode::ArticleSearch {
+            self.mode = AppMode::Normal;
+        }
+    }
+
+    /// Move through the matches, wrapping around at either end
+    pub fn next_article_match(&mut self, delta: isize) {
+        let matches = reader::match_lines(&self.article_lines(), &self.article_query);
+        let Some(current) = self.article_match.filter(|&index| index < matches.len()) else {
+            return self.find_article_match();
+        };
+        let index = (current as isize + delta).rem_euclid(matches.len() as isize) as usize;
+        self.article_match = Some(index);
+        self.scroll_to_article_line(matches[index]);
+    }
+
+    // Jump to the first match from the top of the view, or the first one above it
+    fn find_article_match(&mut self) {
+        let matches = reader::match_lines(&self.article_lines(), &self.article_query);
+        let top = self.article_scroll_offset;
+        self.article_match = matches.iter().position(|&line| line >= top).or((!matches.is_empty()).then_some(0));
+        if let Some(index) = self.article_match {
+            self.scroll_to_article_line(matches[index]);
+        }
+    }
+
+    // Show a line with a little of what comes before it
+    fn scroll_to_article_line(&mut self, line: usize) {
+        self.scroll_article_to(line.saturating_sub(ARTICLE_MATCH_CONTEXT));
     }
 
     pub fn get_current_article_text(&self) -> Option<&String> {
@@ -442,19 +877,45 @@ impl App {
         };
     }
 
+    /// Move to the next theme: built-ins first, then user themes
     pub fn cycle_theme(&mut self) {
-        self.theme = match self.theme.name.as_str() {
-            "Light" => Theme::dark(),
-            "Dark" => Theme::light(),
-            _ => Theme::light(),
-        };
+        let current = self.themes.iter().position(|theme| theme.name == self.theme.name);
+        let next = current.map_or(0, |index| (index + 1) % self.themes.len());
+        if let Some(theme) = self.themes.get(next) {
+            self.theme = theme.clone();
+        }
+    }
+
+    pub fn set_theme(&mut self, name: &ThemeName) {
+        self.theme = Theme::from_name(name, &self.themes).for_depth(self.color_depth);
+    }
+
+    /// Replace the available themes and colour depth, keeping the current theme by name
+    pub fn set_themes(&mut self, themes: Vec<Theme>, color_depth: ColorDepth) {
+        self.color_depth = color_depth;
+        self.themes = themes.into_iter().map(|theme| theme.for_depth(color_depth)).collect();
+        self.set_theme(&self.theme.config_name());
     }
 
     pub fn cycle_sort_order(&mut self) {
-        self.sort_order = self.sort_order.next();
+        self.set_sort_order(self.sort_order.next());
+    }
+
+    pub fn set_sort_order(&mut self, order: SortOrder) {
+        self.sort_order = order;
         self.apply_sort();
     }
 
+    pub fn open_palette(&mut self, mode: KeyMode) {
+        self.palette = Some(Palette::new(self, mode));
+        self.mode = AppMode::Palette;
+    }
+
+    pub fn close_palette(&mut self) {
+        self.palette = None;
+        self.mode = AppMode::Normal;
+    }
+
     fn apply_sort(&mut self) {
         match self.sort_order {
             SortOrder::Default => {
@@ -471,11 +932,10 @@ impl App {
         }
         // Reset selection to top after sorting
         self.selected = 0;
+        self.update_search_matches();
     }
 
     pub fn check_auto_refresh(&self) -> bool {
-        // Auto-refresh every 5 minutes (300 seconds)
-        const AUTO_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
         self.last_refresh_time.elapsed() >= AUTO_REFRESH_INTERVAL
     }
 
@@ -504,19 +964,394 @@ impl App {
             self.current_feed = top_stories_feed;
             self.is_loading = true;
             self.selected = 0;
-            return true; // Signal that feed needs to be fetched
+            true // Signal that feed needs to be fetched
         } else {
             // Already on Top Stories, just find and select the ticker article
             if let Some(index) = self.stories.iter().position(|s| s.link == *ticker_url) {
                 self.selected = index;
-                // Clear last opened to allow opening this article
-                self.last_opened_index = None;
-                self.last_selection_change_time = Instant::now();
             } else {
                 // Story not found, select first story
                 self.selected = 0;
             }
+            false
+        }
+    }
+
+    // Search methods
+    pub fn start_search(&mut self) {
+        self.mode = AppMode::Search;
+    }
+
+    pub fn search_push(&mut self, c: char) {
+        self.search_query.push(c);
+        self.update_search_matches();
+        self.jump_to_first_match();
+    }
+
+    pub fn search_pop(&mut self) {
+        self.search_query.pop();
+        self.update_search_matches();
+        self.jump_to_first_match();
+    }
+
+    pub fn confirm_search(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search_query.clear();
+        self.search_matches.clear();
+        self.mode = AppMode::Normal;
+    }
+
+    pub fn is_search_active(&self) -> bool {
+        !self.search_query.is_empty()
+    }
+
+    pub fn is_search_match(&self, index: usize) -> bool {
+        self.search_matches.binary_search(&index).is_ok()
+    }
+
+    // Jump to the next match after the current selection (wraps around)
+    pub fn next_match(&mut self) {
+        let next = self.search_matches.iter()
+            .find(|&&i| i > self.selected)
+            .or_else(|| self.search_matches.first())
+            .copied();
+        if let Some(index) = next {
+            self.set_selected(index);
+        }
+    }
+
+    // Jump to the previous match before the current selection (wraps around)
+    pub fn previous_match(&mut self) {
+        let prev = self.search_matches.iter()
+            .rev()
+            .find(|&&i| i < self.selected)
+            .or_else(|| self.search_matches.last())
+            .copied();
+        if let Some(index) = prev {
+            self.set_selected(index);
+        }
+    }
+
+    // Archive search methods
+    pub fn start_archive_search(&mut self) {
+        self.archive_query.clear();
+        self.mode = AppMode::ArchiveSearch;
+    }
+
+    pub fn archive_search_push(&mut self, c: char) {
+        self.archive_query.push(c);
+    }
+
+    pub fn archive_search_pop(&mut self) {
+        self.archive_query.pop();
+    }
+
+    pub fn cancel_archive_search(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    // Show archive results as a pseudo-feed
+    // Returns true if results need to be fetched (trigger FeedChanged action)
+    pub fn submit_archive_search(&mut self) -> bool {
+        self.mode = AppMode::Normal;
+        if self.archive_query.trim().is_empty() {
             return false;
         }
+
+        let name = format!("Archive: {}", self.archive_query.trim());
+        self.select_feed(Feed::new(&name, crate::feeds::ARCHIVE_FEED_URL));
+        true
+    }
+
+    // Incremental search: keep the selection on a match while typing
+    fn jump_to_first_match(&mut self) {
+        if self.is_search_match(self.selected) {
+            return;
+        }
+        self.next_match();
+    }
+
+    fn update_search_matches(&mut self) {
+        self.search_matches.clear();
+        if self.search_query.is_empty() {
+            return;
+        }
+
+        // Case-insensitive substring match against title and description
+        let query = self.search_query.to_lowercase();
+        self.search_matches = self.stories.iter()
+            .enumerate()
+            .filter(|(_, story)| {
+                story.title.to_lowercase().contains(&query)
+                    || story.description.to_lowercase().contains(&query)
+            })
+            .map(|(i, _)| i)
+            .collect();
+    }
+
+    fn set_selected(&mut self, index: usize) {
+        if index < self.stories.len() {
+            self.selected = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn story(title: &str, description: &str) -> NewsStory {
+        NewsStory {
+            title: title.to_string(),
+            description: description.to_string(),
+            link: format!("https://www.bbc.co.uk/news/{}", title.to_lowercase().replace(' ', "-")),
+            pub_date: String::from("2025-01-01 12:00:00"),
+            category: String::from("News"),
+            image_url: None,
+        }
+    }
+
+    fn app_with_stories() -> App {
+        let mut app = App::new(Theme::light());
+        app.update_stories(vec![
+            story("Election results", "Votes counted overnight"),
+            story("Storm warning", "Heavy rain expected"),
+            story("Markets rally", "Shares rise after election"),
+        ]);
+        app
+    }
+
+    #[test]
+    fn test_cycle_theme_visits_every_theme() {
+        let mut app = App::new(Theme::light());
+        let mut seen = vec![app.theme.name.clone()];
+        for _ in 1..app.themes.len() {
+            app.cycle_theme();
+            seen.push(app.theme.name.clone());
+        }
+        assert_eq!(seen, crate::theme::BUILT_IN_THEMES);
+
+        app.cycle_theme();
+        assert_eq!(app.theme.name, "light");
+    }
+
+    #[test]
+    fn test_search_matches_title_and_description() {
+        let mut app = app_with_stories();
+        for c in "ELECTION".chars() {
+            app.search_push(c);
+        }
+        assert_eq!(app.search_matches, vec![0, 2]);
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn test_search_next_and_previous_wrap() {
+        let mut app = app_with_stories();
+        app.search_push('e');
+        app.search_push('l');
+        app.next_match();
+        assert_eq!(app.selected, 2);
+        app.next_match();
+        assert_eq!(app.selected, 0);
+        app.previous_match();
+        assert_eq!(app.selected, 2);
+    }
+
+    #[test]
+    fn test_toggle_and_mark_all_read() {
+        let mut app = app_with_stories();
+        assert_eq!(app.unread_count(), 3);
+
+        app.toggle_read();
+        assert!(app.is_read(0));
+        assert_eq!(app.unread_count(), 2);
+        app.toggle_read();
+        assert!(!app.is_read(0));
+
+        app.mark_all_read();
+        assert_eq!(app.unread_count(), 0);
+    }
+
+    #[test]
+    fn test_unread_counts_follow_fetches_and_reads() {
+        let mut app = app_with_stories();
+        let top = app.current_feed.url.clone();
+        assert_eq!(app.feed_unread_counts.get(&top), Some(&3));
+
+        // Leaving a feed keeps its count, including stories read since it was fetched
+        app.toggle_read();
+        let world = Feed::new("World", "https://feeds.bbci.co.uk/news/world/rss.xml");
+        app.select_feed(world.clone());
+        assert_eq!(app.feed_unread_counts.get(&top), Some(&2));
+        app.update_stories(vec![story("Ceasefire talks", "")]);
+        assert_eq!(app.feed_unread_counts.get(&world.url), Some(&1));
+
+        // Stories held by other tabs are recounted when the menu opens
+        app.open_tab(Feed::new("Technology", "https://feeds.bbci.co.uk/news/technology/rss.xml"));
+        app.read_state.mark_read("https://www.bbc.co.uk/news/ceasefire-talks");
+        app.toggle_feed_menu();
+        assert_eq!(app.feed_unread_counts.get(&world.url), Some(&0));
+        assert_eq!(app.feed_unread_counts.get(&top), Some(&2));
+    }
+
+    // An article of numbered paragraphs, shown 10 lines at a time
+    fn app_reading_article(paragraphs: usize) -> App {
+        let mut app = app_with_stories();
+        let text: Vec<String> = (1..=paragraphs).map(|i| format!("Paragraph {}", i)).collect();
+        app.article_cache.insert(app.stories[0].link.clone(), text.join("\n"));
+        app.article_view = Size::new(40, 10);
+        app.fetch_and_show_article();
+        app
+    }
+
+    #[test]
+    fn test_article_scroll_is_clamped_and_remembered() {
+        let mut app = app_reading_article(30);
+        app.scroll_article_by(100);
+        assert_eq!(app.article_scroll_offset, 20);
+        app.scroll_article_by(-100);
+        assert_eq!(app.article_scroll_offset, 0);
+
+        // Reopening goes back to where reading stopped, even at another width
+        app.scroll_article_to(5);
+        app.toggle_article_view();
+        app.article_view = Size::new(60, 10);
+        app.fetch_and_show_article();
+        assert_eq!(app.article_scroll_offset, 5);
+
+        // Finished articles start from the top again
+        app.scroll_article_to(usize::MAX);
+        app.toggle_article_view();
+        app.fetch_and_show_article();
+        assert_eq!(app.article_scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_article_search_moves_between_matches() {
+        let mut app = app_reading_article(30);
+        app.start_article_search();
+        for c in "paragraph 2".chars() {
+            app.article_search_push(c);
+        }
+        app.confirm_article_search();
+        // Paragraphs 2 and 20-29 match; each is shown with two lines above it
+        assert_eq!(app.article_match, Some(0));
+        assert_eq!(app.article_scroll_offset, 0);
+
+        app.next_article_match(1);
+        assert_eq!(app.article_scroll_offset, 17);
+        app.next_article_match(-2);
+        assert_eq!(app.article_match, Some(10));
+        assert_eq!(app.article_scroll_offset, 20);
+
+        app.toggle_article_view();
+        assert!(app.article_query.is_empty());
+    }
+
+    #[test]
+    fn test_toggle_bookmark_keeps_article_and_syncs_saved_feed() {
+        let mut app = app_with_stories();
+        let link = app.stories[0].link.clone();
+        app.article_cache.insert(link.clone(), String::from("Article body"));
+
+        app.toggle_bookmark();
+        assert!(app.is_bookmarked(0));
+        assert_eq!(app.bookmarks.article(&link), Some(&String::from("Article body")));
+
+        app.select_feed(crate::feeds::get_saved_feed());
+        app.update_stories(app.bookmarks.stories());
+        assert_eq!(app.stories.len(), 1);
+
+        app.toggle_bookmark();
+        assert!(app.bookmarks.is_empty());
+        assert!(app.stories.is_empty());
+    }
+
+    #[test]
+    fn test_bookmark_without_article_is_filled_in_later() {
+        let mut app = app_with_stories();
+        let link = app.stories[1].link.clone();
+        app.selected = 1;
+
+        app.toggle_bookmark();
+        assert!(app.is_bookmarked(1));
+        assert_eq!(app.bookmarks.article(&link), None);
+        assert_eq!(app.bookmarks.missing_articles(), vec![link.clone()]);
+
+        // Opening the article stores it with the bookmark
+        app.article_cache.insert(link.clone(), String::from("Article body"));
+        app.fetch_and_show_article();
+        assert_eq!(app.bookmarks.article(&link), Some(&String::from("Article body")));
+        assert!(app.bookmarks.missing_articles().is_empty());
+    }
+
+    #[test]
+    fn test_search_survives_refresh_until_cleared() {
+        let mut app = app_with_stories();
+        app.search_push('s');
+        app.search_push('t');
+        app.confirm_search();
+        app.update_stories(vec![story("Storm moves north", ""), story("Election", "")]);
+        assert_eq!(app.search_matches, vec![0]);
+
+        app.clear_search();
+        assert!(!app.is_search_active());
+        assert!(app.search_matches.is_empty());
+    }
+
+    #[test]
+    fn test_config_error_fits_on_one_line() {
+        let mut app = App::new(Theme::light());
+        let error = crate::config::parse_config("[keybindings]\nquit = 'hyper-q'\n").unwrap_err();
+        app.set_config_error(&error);
+
+        let message = app.config_message.unwrap();
+        assert!(!message.contains('\n'));
+        assert!(message.contains("line 2, column 8"), "{}", message);
+        assert!(message.ends_with("unknown key `hyper-q`"), "{}", message);
+    }
+
+    #[test]
+    fn test_config_warnings_clear_but_errors_stay() {
+        let mut app = App::new(Theme::light());
+        app.set_config_warnings(&["unknown key `colour`".to_string()]);
+        assert!(app.config_message.is_some());
+        for _ in 0..CONFIG_WARNING_TICKS {
+            app.tick();
+        }
+        assert_eq!(app.config_message, None);
+
+        app.set_config_warnings(&["unknown key `colour`".to_string()]);
+        app.dismiss_config_warning();
+        assert_eq!(app.config_message, None);
+
+        let error = crate::config::parse_config("theme = ").unwrap_err();
+        app.set_config_error(&error);
+        for _ in 0..CONFIG_WARNING_TICKS * 2 {
+            app.tick();
+        }
+        app.dismiss_config_warning();
+        assert!(app.config_message.is_some());
+    }
+
+    #[test]
+    fn test_resize_preview_stays_in_bounds() {
+        let mut app = App::new(Theme::light());
+        app.resize_preview(2);
+        assert!(app.show_preview);
+        assert_eq!(app.layout.preview_percent, 30);
+        app.resize_preview(-20);
+        assert_eq!(app.layout.preview_percent, 10);
+
+        // The three-pane layout gives the reading pane room from the story list
+        app.toggle_layout();
+        app.resize_preview(1);
+        assert_eq!((app.layout.list_percent, app.layout.preview_percent), (40, 10));
+        app.resize_preview(100);
+        assert_eq!(app.layout.list_percent, 10);
     }
 }
@@ -0,0 +1,301 @@
+use anyhow::{Context, Result};
+use chrono::{NaiveDate, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+use crate::app::NewsStory;
+use crate::paths::data_dir;
+use crate::date_utils::parse_duration;
+use crate::feeds::get_all_feeds;
+
+const ARCHIVE_FILE: &str = "archive.db";
+const MAX_SEARCH_RESULTS: usize = 100;
+
+/// A story from the archive with when and where it was seen
+#[derive(Debug, Clone)]
+pub struct ArchivedStory {
+    pub story: NewsStory,
+    pub first_seen: i64,     // Unix timestamp
+    pub last_seen: i64,      // Unix timestamp
+    pub feeds: Vec<String>,  // Feed names the story appeared in
+}
+
+/// Archive search filters
+#[derive(Debug, Default)]
+pub struct ArchiveQuery {
+    pub text: String,
+    pub since: Option<i64>,       // Only stories seen since this Unix timestamp
+    pub feed: Option<String>,     // Only stories that appeared in this feed (by name)
+}
+
+/// Append-only archive of every story and article body ever fetched
+pub struct Archive {
+    conn: Connection,
+}
+
+impl Archive {
+    /// Open the archive in the data directory
+    pub fn open() -> Result<Self> {
+        let dir = data_dir()?;
+        std::fs::create_dir_all(&dir)?;
+        Self::open_at(&dir.join(ARCHIVE_FILE))
+    }
+
+    pub fn open_at(path: &Path) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open archive at {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS stories (
+                link        TEXT PRIMARY KEY,
+                title       TEXT NOT NULL,
+                description TEXT NOT NULL,
+                pub_date    TEXT NOT NULL,
+                category    TEXT NOT NULL,
+                image_url   TEXT,
+                first_seen  INTEGER NOT NULL,
+                last_seen   INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS story_feeds (
+                link      TEXT NOT NULL,
+                feed_name TEXT NOT NULL,
+                PRIMARY KEY (link, feed_name)
+            );
+            CREATE TABLE IF NOT EXISTS articles (
+                link       TEXT PRIMARY KEY,
+                content    TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL
+            );
+            CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5(
+                link UNINDEXED, title, description, body
+            );",
+        )
+        .context("Failed to initialise archive")?;
+
+        Ok(Self { conn })
+    }
+
+    /// Record stories seen in a feed, keeping first-seen times and feed membership
+    pub fn record_stories(&mut self, feed_url: &str, stories: &[NewsStory]) -> Result<()> {
+        let now = Utc::now().timestamp();
+        let feed_name = feed_name_for_url(feed_url);
+        let tx = self.conn.transaction()?;
+
+        for story in stories {
+            tx.execute(
+                "INSERT INTO stories (link, title, description, pub_date, category, image_url, first_seen, last_seen)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+                 ON CONFLICT(link) DO UPDATE SET
+                    title = excluded.title,
+                    description = excluded.description,
+                    pub_date = excluded.pub_date,
+                    category = excluded.category,
+                    image_url = excluded.image_url,
+                    last_seen = excluded.last_seen",
+                params![story.link, story.title, story.description, story.pub_date, story.category, story.image_url, now],
+            )?;
+            tx.execute(
+                "INSERT OR IGNORE INTO story_feeds (link, feed_name) VALUES (?1, ?2)",
+                params![story.link, feed_name],
+            )?;
+            Self::update_search_index(&tx, &story.link)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Record a fetched article body
+    pub fn record_article(&mut self, link: &str, content: &str) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO articles (link, content, fetched_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(link) DO UPDATE SET content = excluded.content, fetched_at = excluded.fetched_at",
+            params![link, content, Utc::now().timestamp()],
+        )?;
+        Self::update_search_index(&tx, link)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    // Rebuild the full-text entry for a story from its current title, description and body
+    fn update_search_index(conn: &Connection, link: &str) -> Result<()> {
+        conn.execute("DELETE FROM search_index WHERE link = ?1", params![link])?;
+        conn.execute(
+            "INSERT INTO search_index (link, title, description, body)
+             SELECT s.link, s.title, s.description, COALESCE(a.content, '')
+             FROM stories s LEFT JOIN articles a ON a.link = s.link
+             WHERE s.link = ?1",
+            params![link],
+        )?;
+        Ok(())
+    }
+
+    /// Stored article body for a story, if it was ever fetched
+    pub fn article(&self, link: &str) -> Option<String> {
+        self.conn
+            .query_row("SELECT content FROM articles WHERE link = ?1", params![link], |row| row.get(0))
+            .ok()
+    }
+
+    /// Full-text search, best matches first
+    pub fn search(&self, query: &ArchiveQuery) -> Result<Vec<ArchivedStory>> {
+        let match_expr = fts_match_expression(&query.text);
+        if match_expr.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT s.link, s.title, s.description, s.pub_date, s.category, s.image_url, s.first_seen, s.last_seen,
+                    (SELECT group_concat(feed_name, '|') FROM story_feeds f WHERE f.link = s.link)
+             FROM search_index
+             JOIN stories s ON s.link = search_index.link
+             WHERE search_index MATCH ?1
+               AND (?2 IS NULL OR s.last_seen >= ?2)
+               AND (?3 IS NULL OR EXISTS (
+                    SELECT 1 FROM story_feeds f WHERE f.link = s.link AND f.feed_name = ?3))
+             ORDER BY rank
+             LIMIT ?4",
+        )?;
+
+        let rows = stmt.query_map(
+            params![match_expr, query.since, query.feed, MAX_SEARCH_RESULTS as i64],
+            |row| {
+                let feeds: Option<String> = row.get(8)?;
+                Ok(ArchivedStory {
+                    story: NewsStory {
+                        link: row.get(0)?,
+                        title: row.get(1)?,
+                        description: row.get(2)?,
+                        pub_date: row.get(3)?,
+                        category: row.get(4)?,
+                        image_url: row.get(5)?,
+                    },
+                    first_seen: row.get(6)?,
+                    last_seen: row.get(7)?,
+                    feeds: feeds
+                        .map(|f| f.split('|').map(String::from).collect())
+                        .unwrap_or_default(),
+                })
+            },
+        )?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to search archive")
+    }
+}
+
+// Turn free text into an FTS5 query: every word must match, as a prefix
+fn fts_match_expression(text: &str) -> String {
+    text.split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn feed_name_for_url(feed_url: &str) -> String {
+    get_all_feeds()
+        .into_iter()
+        .find(|feed| feed.url == feed_url)
+        .map(|feed| feed.name)
+        .unwrap_or_else(|| feed_url.to_string())
+}
+
+/// Parse a `--since` value: a relative age ("30m", "12h", "7d", "2w") or a date ("2025-01-31")
+pub fn parse_since(value: &str) -> Result<i64> {
+    let value = value.trim();
+
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        let midnight = date.and_hms_opt(0, 0, 0).context("Invalid date")?;
+        return Ok(Utc.from_utc_datetime(&midnight).timestamp());
+    }
+
+    let age = parse_duration(value)
+        .with_context(|| format!("Invalid --since value: '{}'. Use e.g. 12h, 7d, 2w or 2025-01-31", value))?;
+    Ok(Utc::now().timestamp() - age.as_secs() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORLD_URL: &str = "https://feeds.bbci.co.uk/news/world/rss.xml";
+    const TECH_URL: &str = "https://feeds.bbci.co.uk/news/technology/rss.xml";
+
+    fn story(link: &str, title: &str) -> NewsStory {
+        NewsStory {
+            title: title.to_string(),
+            description: String::from("A description"),
+            link: link.to_string(),
+            pub_date: String::from("2025-01-01 12:00:00"),
+            category: String::from("News"),
+            image_url: None,
+        }
+    }
+
+    fn search(archive: &Archive, text: &str) -> Vec<ArchivedStory> {
+        archive.search(&ArchiveQuery { text: text.to_string(), ..Default::default() }).unwrap()
+    }
+
+    #[test]
+    fn test_records_feeds_and_keeps_first_seen() {
+        let mut archive = Archive::open_in_memory().unwrap();
+        archive.record_stories(WORLD_URL, &[story("https://bbc.co.uk/1", "Chip shortage eases")]).unwrap();
+        let first = search(&archive, "chip");
+
+        archive.record_stories(TECH_URL, &[story("https://bbc.co.uk/1", "Chip shortage eases further")]).unwrap();
+        let results = search(&archive, "chip");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].story.title, "Chip shortage eases further");
+        assert_eq!(results[0].first_seen, first[0].first_seen);
+        assert_eq!(results[0].feeds.len(), 2);
+        assert!(results[0].feeds.contains(&String::from("Technology")));
+    }
+
+    #[test]
+    fn test_search_article_body_and_feed_filter() {
+        let mut archive = Archive::open_in_memory().unwrap();
+        archive.record_stories(WORLD_URL, &[story("https://bbc.co.uk/1", "Summit opens")]).unwrap();
+        archive.record_stories(TECH_URL, &[story("https://bbc.co.uk/2", "New phone launched")]).unwrap();
+        archive.record_article("https://bbc.co.uk/1", "Leaders discussed semiconductors at length").unwrap();
+
+        let results = search(&archive, "semiconductor");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].story.link, "https://bbc.co.uk/1");
+        assert_eq!(archive.article("https://bbc.co.uk/1").unwrap(), "Leaders discussed semiconductors at length");
+
+        let filtered = archive.search(&ArchiveQuery {
+            text: String::from("launched"),
+            feed: Some(String::from("World")),
+            ..Default::default()
+        }).unwrap();
+        assert!(filtered.is_empty());
+    }
+
+    #[test]
+    fn test_search_handles_fts_syntax_in_query() {
+        let mut archive = Archive::open_in_memory().unwrap();
+        archive.record_stories(WORLD_URL, &[story("https://bbc.co.uk/1", "Rates \"held\" at 5%")]).unwrap();
+        assert_eq!(search(&archive, "\"held\" AND (5%").len(), 0);
+        assert_eq!(search(&archive, "rates held").len(), 1);
+        assert!(search(&archive, "   ").is_empty());
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = Utc::now().timestamp();
+        assert!((parse_since("2h").unwrap() - (now - 7200)).abs() <= 1);
+        assert!((parse_since("7d").unwrap() - (now - 7 * 86400)).abs() <= 1);
+        assert_eq!(parse_since("2025-01-31").unwrap(), 1738281600);
+        assert!(parse_since("soon").is_err());
+        assert!(parse_since("3y").is_err());
+    }
+}
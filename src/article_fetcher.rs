@@ -4,15 +4,29 @@ use dom_smoothie::Readability;
 use html2text::from_read;
 use std::time::Duration;
 
+use crate::archive::Archive;
 use crate::cache::Cache;
 
+/// Where an article's text came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArticleSource {
+    Cache,   // Cached and still fresh
+    Network, // Just downloaded
+    Stale,   // Expired cache entry or archive copy, because the network failed
+}
+
 pub fn fetch_article_content(url: &str) -> Result<String> {
+    fetch_article(url).map(|(content, _)| content)
+}
+
+/// Article text and where it came from
+pub fn fetch_article(url: &str) -> Result<(String, ArticleSource)> {
     let cache = Cache::new().ok();
 
     // Try to load from cache first
     if let Some(ref cache) = cache {
         if let Some(cached_content) = cache.load_article(url) {
-            return Ok(cached_content);
+            return Ok((cached_content, ArticleSource::Cache));
         }
     }
 
@@ -25,15 +39,23 @@ pub fn fetch_article_content(url: &str) -> Result<String> {
             if let Some(ref cache) = cache {
                 let _ = cache.save_article(url, &content);
             }
-            Ok(content)
+            // Keep every article body ever fetched in the local archive
+            if let Ok(mut archive) = Archive::open() {
+                let _ = archive.record_article(url, &content);
+            }
+            Ok((content, ArticleSource::Network))
         }
         Err(e) => {
             // Network failed, try to load from cache (even if expired)
             if let Some(ref cache) = cache {
                 if let Some(cached_content) = cache.load_article_offline(url) {
-                    return Ok(cached_content);
+                    return Ok((cached_content, ArticleSource::Stale));
                 }
             }
+            // Last resort: the archive keeps article bodies after the cache is cleared
+            if let Some(archived_content) = Archive::open().ok().and_then(|a| a.article(url)) {
+                return Ok((archived_content, ArticleSource::Stale));
+            }
             Err(e)
         }
     }
@@ -0,0 +1,131 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::NewsStory;
+use crate::cache::write_atomic;
+use crate::paths::data_dir;
+
+const BOOKMARKS_FILE: &str = "bookmarks.bin";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub story: NewsStory,
+    pub feed_name: String,       // Feed the story was saved from
+    pub saved_at: u64,           // Unix timestamp
+    pub article: Option<String>, // Article body for offline reading
+}
+
+/// Saved stories (read-later list), persisted across sessions
+#[derive(Default)]
+pub struct Bookmarks {
+    path: Option<PathBuf>,
+    items: Vec<Bookmark>, // Most recently saved first
+}
+
+impl Bookmarks {
+    /// Load bookmarks from the data directory (empty if missing or unreadable)
+    pub fn load() -> Self {
+        let path = match data_dir() {
+            Ok(dir) => dir.join(BOOKMARKS_FILE),
+            Err(_) => return Self::default(),
+        };
+
+        let items = fs::read(&path)
+            .ok()
+            .and_then(|data| bincode::deserialize(&data).ok())
+            .unwrap_or_default();
+
+        Self { path: Some(path), items }
+    }
+
+    pub fn items(&self) -> &[Bookmark] {
+        &self.items
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn contains(&self, link: &str) -> bool {
+        self.items.iter().any(|b| b.story.link == link)
+    }
+
+    /// Saved stories, for display as the "Saved" feed
+    pub fn stories(&self) -> Vec<NewsStory> {
+        self.items.iter().map(|b| b.story.clone()).collect()
+    }
+
+    /// Cached article body for a saved story
+    pub fn article(&self, link: &str) -> Option<&String> {
+        self.items.iter()
+            .find(|b| b.story.link == link)
+            .and_then(|b| b.article.as_ref())
+    }
+
+    pub fn add(&mut self, story: &NewsStory, feed_name: &str, article: Option<String>) -> Result<()> {
+        if self.contains(&story.link) {
+            return Ok(());
+        }
+
+        let saved_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        self.items.insert(0, Bookmark {
+            story: story.clone(),
+            feed_name: feed_name.to_string(),
+            saved_at,
+            article,
+        });
+        self.save()
+    }
+
+    /// Store the article body for a story saved before it was downloaded
+    pub fn fill_article(&mut self, link: &str, article: &str) -> Result<()> {
+        let Some(bookmark) = self.items.iter_mut().find(|b| b.story.link == link && b.article.is_none()) else {
+            return Ok(());
+        };
+        bookmark.article = Some(article.to_string());
+        self.save()
+    }
+
+    /// Links of saved stories still waiting for their article body
+    pub fn missing_articles(&self) -> Vec<String> {
+        self.items.iter()
+            .filter(|b| b.article.is_none())
+            .map(|b| b.story.link.clone())
+            .collect()
+    }
+
+    /// Remove a bookmark by link, returns the removed bookmark if it existed
+    pub fn remove(&mut self, link: &str) -> Result<Option<Bookmark>> {
+        let Some(index) = self.items.iter().position(|b| b.story.link == link) else {
+            return Ok(None);
+        };
+        let removed = self.items.remove(index);
+        self.save()?;
+        Ok(Some(removed))
+    }
+
+    fn save(&self) -> Result<()> {
+        // In-memory only (e.g. no home directory)
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        write_atomic(path, &bincode::serialize(&self.items)?)?;
+        Ok(())
+    }
+}
@@ -1,13 +1,26 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::app::NewsStory;
 
 const CACHE_EXPIRY_SECS: u64 = 900; // 15 minutes
 
+// On-disk format: MAGIC, then FORMAT_VERSION as little-endian u32, then the bincode payload.
+// Bump FORMAT_VERSION whenever CachedFeed, CachedArticle or NewsStory change shape.
+// Version 1 files predate the header (bare bincode, keyed by DefaultHasher).
+const MAGIC: &[u8; 4] = b"BBCL";
+const FORMAT_VERSION: u32 = 2;
+const HEADER_LEN: usize = MAGIC.len() + 4;
+const KEY_LEN: usize = 64; // Hex SHA-256
+
+const LOCK_FILE: &str = ".lock";
+const TEMP_SUFFIX: &str = ".tmp";
+const STALE_TEMP_SECS: u64 = 60; // Temp files older than this were left by a crash
+
 #[derive(Serialize, Deserialize)]
 struct CachedFeed {
     stories: Vec<NewsStory>,
@@ -22,24 +35,94 @@ struct CachedArticle {
     url: String,
 }
 
+#[derive(Serialize, Deserialize)]
+struct CachedImage {
+    bytes: Vec<u8>,
+    timestamp: u64,
+    url: String,
+}
+
+/// Kind of cache entry, determined by file name prefix
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EntryKind {
+    Feed,
+    Article,
+    Image,
+}
+
+impl EntryKind {
+    pub fn name(&self) -> &str {
+        match self {
+            EntryKind::Feed => "Feeds",
+            EntryKind::Article => "Articles",
+            EntryKind::Image => "Images",
+        }
+    }
+
+    fn from_file_name(name: &str) -> Option<Self> {
+        if !name.ends_with(".bin") {
+            None
+        } else if name.starts_with("feed_") {
+            Some(EntryKind::Feed)
+        } else if name.starts_with("article_") {
+            Some(EntryKind::Article)
+        } else if name.starts_with("image_") {
+            Some(EntryKind::Image)
+        } else {
+            None
+        }
+    }
+}
+
+/// Statistics for one kind of cache entry
+#[derive(Debug, Default)]
+pub struct EntryStats {
+    pub count: usize,
+    pub total_bytes: u64,
+    pub oldest: Option<SystemTime>,
+    pub newest: Option<SystemTime>,
+}
+
+/// Result of pruning the cache
+#[derive(Debug, Default)]
+pub struct PruneReport {
+    pub removed: usize,
+    pub freed_bytes: u64,
+}
+
+struct CacheEntry {
+    path: PathBuf,
+    kind: EntryKind,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// Advisory lock on the cache directory, released on drop
+struct CacheLock {
+    _file: fs::File,
+}
+
 pub struct Cache {
     cache_dir: PathBuf,
 }
 
 impl Cache {
     pub fn new() -> Result<Self> {
-        // Use ~/.bbcli/cache for consistency with config location
-        let home = dirs::home_dir()
-            .ok_or_else(|| anyhow::anyhow!("Could not determine home directory"))?;
-
-        let cache_dir = home.join(".bbcli").join("cache");
+        Self::with_dir(crate::paths::cache_dir()?)
+    }
 
+    /// Use a specific cache directory
+    pub fn with_dir(cache_dir: PathBuf) -> Result<Self> {
         // Create cache directory if it doesn't exist
         fs::create_dir_all(&cache_dir)?;
 
         Ok(Cache { cache_dir })
     }
 
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
     /// Get current Unix timestamp
     fn current_timestamp() -> u64 {
         SystemTime::now()
@@ -50,54 +133,124 @@ impl Cache {
 
     /// Get cache file path for a feed
     fn feed_cache_path(&self, feed_url: &str) -> PathBuf {
-        let hash = Self::hash_string(feed_url);
-        self.cache_dir.join(format!("feed_{}.bin", hash))
+        let key = Self::cache_key(feed_url);
+        self.cache_dir.join(format!("feed_{}.bin", key))
     }
 
     /// Get cache file path for an article
     fn article_cache_path(&self, article_url: &str) -> PathBuf {
-        let hash = Self::hash_string(article_url);
-        self.cache_dir.join(format!("article_{}.bin", hash))
+        let key = Self::cache_key(article_url);
+        self.cache_dir.join(format!("article_{}.bin", key))
+    }
+
+    /// Get cache file path for an image
+    fn image_cache_path(&self, image_url: &str) -> PathBuf {
+        let key = Self::cache_key(image_url);
+        self.cache_dir.join(format!("image_{}.bin", key))
     }
 
-    /// Simple hash function for URLs
-    fn hash_string(s: &str) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
+    /// Stable cache key: hex SHA-256 of the normalised URL
+    fn cache_key(url: &str) -> String {
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(normalize_url(url).as_bytes());
+        digest.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Serialize with the format header
+    fn encode<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+        let mut data = Vec::with_capacity(HEADER_LEN);
+        data.extend_from_slice(MAGIC);
+        data.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bincode::serialize_into(&mut data, value)?;
+        Ok(data)
+    }
+
+    /// Deserialize, rejecting files without a header or from another format version
+    fn decode<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Option<T> {
+        if data.len() < HEADER_LEN || &data[..MAGIC.len()] != MAGIC {
+            return None;
+        }
+        let version = u32::from_le_bytes(data[MAGIC.len()..HEADER_LEN].try_into().ok()?);
+        if version != FORMAT_VERSION {
+            return None;
+        }
+        bincode::deserialize(&data[HEADER_LEN..]).ok()
+    }
 
-        let mut hasher = DefaultHasher::new();
-        s.hash(&mut hasher);
-        format!("{:x}", hasher.finish())
+    /// Take the cache write lock (blocks while another bbcli instance is writing)
+    fn lock(&self) -> Result<CacheLock> {
+        let file = fs::File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.cache_dir.join(LOCK_FILE))?;
+        file.lock()?;
+        Ok(CacheLock { _file: file })
+    }
+
+    /// Write an entry under the lock, atomically
+    fn write_entry(&self, path: &Path, data: &[u8]) -> Result<()> {
+        let _lock = self.lock()?;
+        write_atomic(path, data)
+    }
+
+    /// Read and decode an entry, removing it if it can't be decoded
+    fn read_entry<T: for<'de> Deserialize<'de>>(&self, path: &Path) -> Option<T> {
+        let data = fs::read(path).ok()?;
+        if let Some(decoded) = Self::decode(&data) {
+            return Some(decoded);
+        }
+
+        // Wrong version or corrupt (e.g. torn write from an older bbcli): invalidate so it gets refetched.
+        // Re-check under the lock so we don't delete an entry another instance just replaced.
+        let _lock = self.lock().ok()?;
+        let data = fs::read(path).ok()?;
+        let decoded = Self::decode(&data);
+        if decoded.is_none() {
+            let _ = fs::remove_file(path);
+        }
+        decoded
+    }
+
+    fn read_feed(&self, feed_url: &str) -> Option<CachedFeed> {
+        self.read_entry(&self.feed_cache_path(feed_url))
+    }
+
+    fn read_article(&self, article_url: &str) -> Option<CachedArticle> {
+        self.read_entry(&self.article_cache_path(article_url))
     }
 
     /// Save feed to cache
     pub fn save_feed(&self, feed_url: &str, stories: &[NewsStory]) -> Result<()> {
+        self.write_feed(feed_url, stories, Self::current_timestamp())
+    }
+
+    /// Store a feed fetched long enough ago that it has expired
+    #[cfg(test)]
+    pub(crate) fn save_expired_feed(&self, feed_url: &str, stories: &[NewsStory], age_secs: u64) -> Result<()> {
+        self.write_feed(feed_url, stories, Self::current_timestamp() - CACHE_EXPIRY_SECS - age_secs)
+    }
+
+    fn write_feed(&self, feed_url: &str, stories: &[NewsStory], timestamp: u64) -> Result<()> {
         let cached_feed = CachedFeed {
             stories: stories.to_vec(),
-            timestamp: Self::current_timestamp(),
+            timestamp,
             feed_url: feed_url.to_string(),
         };
 
         let path = self.feed_cache_path(feed_url);
-        let encoded = bincode::serialize(&cached_feed)?;
-        fs::write(path, encoded)?;
+        self.write_entry(&path, &Self::encode(&cached_feed)?)?;
 
         Ok(())
     }
 
     /// Load feed from cache if not expired
     pub fn load_feed(&self, feed_url: &str) -> Option<Vec<NewsStory>> {
-        let path = self.feed_cache_path(feed_url);
-
-        if !path.exists() {
-            return None;
-        }
-
-        let data = fs::read(&path).ok()?;
-        let cached_feed: CachedFeed = bincode::deserialize(&data).ok()?;
+        let cached_feed = self.read_feed(feed_url)?;
 
         // Check if cache is expired
-        let age = Self::current_timestamp() - cached_feed.timestamp;
+        let age = Self::current_timestamp().saturating_sub(cached_feed.timestamp);
         if age > CACHE_EXPIRY_SECS {
             return None;
         }
@@ -107,16 +260,7 @@ impl Cache {
 
     /// Load feed from cache regardless of expiry (for offline mode)
     pub fn load_feed_offline(&self, feed_url: &str) -> Option<Vec<NewsStory>> {
-        let path = self.feed_cache_path(feed_url);
-
-        if !path.exists() {
-            return None;
-        }
-
-        let data = fs::read(&path).ok()?;
-        let cached_feed: CachedFeed = bincode::deserialize(&data).ok()?;
-
-        Some(cached_feed.stories)
+        self.read_feed(feed_url).map(|cached_feed| cached_feed.stories)
     }
 
     /// Save article content to cache
@@ -128,25 +272,17 @@ impl Cache {
         };
 
         let path = self.article_cache_path(article_url);
-        let encoded = bincode::serialize(&cached_article)?;
-        fs::write(path, encoded)?;
+        self.write_entry(&path, &Self::encode(&cached_article)?)?;
 
         Ok(())
     }
 
     /// Load article from cache if not expired
     pub fn load_article(&self, article_url: &str) -> Option<String> {
-        let path = self.article_cache_path(article_url);
-
-        if !path.exists() {
-            return None;
-        }
-
-        let data = fs::read(&path).ok()?;
-        let cached_article: CachedArticle = bincode::deserialize(&data).ok()?;
+        let cached_article = self.read_article(article_url)?;
 
         // Check if cache is expired (articles cache for longer - 1 hour)
-        let age = Self::current_timestamp() - cached_article.timestamp;
+        let age = Self::current_timestamp().saturating_sub(cached_article.timestamp);
         if age > 3600 {
             return None;
         }
@@ -156,38 +292,441 @@ impl Cache {
 
     /// Load article from cache regardless of expiry (for offline mode)
     pub fn load_article_offline(&self, article_url: &str) -> Option<String> {
-        let path = self.article_cache_path(article_url);
+        self.read_article(article_url).map(|cached_article| cached_article.content)
+    }
 
-        if !path.exists() {
-            return None;
-        }
+    /// Save downloaded image bytes to cache
+    pub fn save_image(&self, image_url: &str, bytes: &[u8]) -> Result<()> {
+        let cached_image = CachedImage {
+            bytes: bytes.to_vec(),
+            timestamp: Self::current_timestamp(),
+            url: image_url.to_string(),
+        };
 
-        let data = fs::read(&path).ok()?;
-        let cached_article: CachedArticle = bincode::deserialize(&data).ok()?;
+        let path = self.image_cache_path(image_url);
+        self.write_entry(&path, &Self::encode(&cached_image)?)?;
 
-        Some(cached_article.content)
+        Ok(())
+    }
+
+    /// Load image bytes from cache (images don't change, so no expiry)
+    pub fn load_image(&self, image_url: &str) -> Option<Vec<u8>> {
+        self.read_entry::<CachedImage>(&self.image_cache_path(image_url))
+            .map(|cached_image| cached_image.bytes)
     }
 
     /// Get cache age in seconds for a feed
     pub fn get_feed_age(&self, feed_url: &str) -> Option<u64> {
-        let path = self.feed_cache_path(feed_url);
+        let cached_feed = self.read_feed(feed_url)?;
+        Some(Self::current_timestamp().saturating_sub(cached_feed.timestamp))
+    }
 
-        if !path.exists() {
-            return None;
+    /// Re-key and re-encode version 1 entries (no header, DefaultHasher keys).
+    /// Entries that can't be migrated are removed. Returns the number migrated.
+    pub fn migrate(&self) -> Result<usize> {
+        let _lock = self.lock()?;
+        let mut migrated = 0;
+
+        for entry in self.entries()? {
+            let name = entry.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let key = name.split_once('_').map(|(_, key)| key).unwrap_or_default();
+            if key.len() == KEY_LEN {
+                continue; // Current key scheme
+            }
+
+            let data = fs::read(&entry.path)?;
+            let result = match entry.kind {
+                EntryKind::Feed => bincode::deserialize::<CachedFeed>(&data).ok().map(|feed| {
+                    (self.feed_cache_path(&feed.feed_url), Self::encode(&feed))
+                }),
+                EntryKind::Article => bincode::deserialize::<CachedArticle>(&data).ok().map(|article| {
+                    (self.article_cache_path(&article.url), Self::encode(&article))
+                }),
+                // Images were never cached in version 1
+                EntryKind::Image => None,
+            };
+
+            if let Some((new_path, Ok(encoded))) = result {
+                // Don't overwrite a newer entry that already uses the stable key
+                if !new_path.exists() {
+                    write_atomic(&new_path, &encoded)?;
+                    // Keep the original age so expiry and pruning still apply
+                    if let Ok(file) = fs::File::options().write(true).open(&new_path) {
+                        let _ = file.set_modified(entry.modified);
+                    }
+                    migrated += 1;
+                }
+            }
+            fs::remove_file(&entry.path)?;
         }
 
-        let data = fs::read(&path).ok()?;
-        let cached_feed: CachedFeed = bincode::deserialize(&data).ok()?;
-
-        Some(Self::current_timestamp() - cached_feed.timestamp)
+        Ok(migrated)
     }
 
     /// Clear all cache
     pub fn clear_all(&self) -> Result<()> {
-        if self.cache_dir.exists() {
-            fs::remove_dir_all(&self.cache_dir)?;
-            fs::create_dir_all(&self.cache_dir)?;
+        // Remove entries rather than the directory so the lock file stays valid for other instances
+        let _lock = self.lock()?;
+        for entry in self.entries()? {
+            fs::remove_file(&entry.path)?;
+        }
+        Ok(())
+    }
+
+    /// Remove temp files left behind by a crash mid-write
+    fn remove_stale_temp_files(&self) -> Result<usize> {
+        let mut removed = 0;
+        let now = SystemTime::now();
+
+        for dir_entry in fs::read_dir(&self.cache_dir)? {
+            let dir_entry = dir_entry?;
+            if !dir_entry.file_name().to_string_lossy().ends_with(TEMP_SUFFIX) {
+                continue;
+            }
+            let modified = dir_entry.metadata()?.modified().unwrap_or(UNIX_EPOCH);
+            let age = now.duration_since(modified).unwrap_or_default();
+            if age > Duration::from_secs(STALE_TEMP_SECS) && fs::remove_file(dir_entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+
+        Ok(removed)
+    }
+
+    // List feed and article entries on disk
+    fn entries(&self) -> Result<Vec<CacheEntry>> {
+        let mut entries = Vec::new();
+
+        for dir_entry in fs::read_dir(&self.cache_dir)? {
+            let dir_entry = dir_entry?;
+            let name = dir_entry.file_name();
+            let Some(kind) = EntryKind::from_file_name(&name.to_string_lossy()) else {
+                continue;
+            };
+            let metadata = dir_entry.metadata()?;
+            entries.push(CacheEntry {
+                path: dir_entry.path(),
+                kind,
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(UNIX_EPOCH),
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// Entry counts, sizes and age range for each kind of entry
+    pub fn stats(&self) -> Result<Vec<(EntryKind, EntryStats)>> {
+        let mut stats = vec![
+            (EntryKind::Feed, EntryStats::default()),
+            (EntryKind::Article, EntryStats::default()),
+            (EntryKind::Image, EntryStats::default()),
+        ];
+
+        for entry in self.entries()? {
+            let Some((_, kind_stats)) = stats.iter_mut().find(|(kind, _)| *kind == entry.kind) else {
+                continue;
+            };
+            kind_stats.count += 1;
+            kind_stats.total_bytes += entry.size;
+            kind_stats.oldest = Some(kind_stats.oldest.map_or(entry.modified, |t| t.min(entry.modified)));
+            kind_stats.newest = Some(kind_stats.newest.map_or(entry.modified, |t| t.max(entry.modified)));
         }
+
+        Ok(stats)
+    }
+
+    /// Remove entries older than `max_age`, then the oldest entries until under `max_bytes`
+    pub fn prune(&self, max_age: Option<Duration>, max_bytes: Option<u64>) -> Result<PruneReport> {
+        let _lock = self.lock()?;
+        let mut report = PruneReport::default();
+        self.remove_stale_temp_files()?;
+        let mut entries = self.entries()?;
+
+        // Oldest first
+        entries.sort_by_key(|entry| entry.modified);
+
+        let now = SystemTime::now();
+        let mut total_bytes: u64 = entries.iter().map(|entry| entry.size).sum();
+
+        for entry in entries {
+            let age = now.duration_since(entry.modified).unwrap_or_default();
+            let too_old = max_age.is_some_and(|max| age > max);
+            let too_big = max_bytes.is_some_and(|max| total_bytes > max);
+
+            if !too_old && !too_big {
+                continue;
+            }
+
+            if fs::remove_file(&entry.path).is_ok() {
+                report.removed += 1;
+                report.freed_bytes += entry.size;
+                total_bytes -= entry.size;
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Write a file via a temp file and rename, so readers never see a partial file
+/// and a crash mid-write leaves the previous contents intact
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<()> {
+    let file_name = path.file_name()
+        .ok_or_else(|| anyhow::anyhow!("Invalid cache path: {}", path.display()))?
+        .to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.{}{}", file_name, std::process::id(), TEMP_SUFFIX));
+
+    let result = (|| -> Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        fs::rename(&temp_path, path)?;
         Ok(())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Normalise a URL so equivalent forms share a cache key
+/// (case-insensitive scheme and host, default port and fragment removed)
+fn normalize_url(url: &str) -> String {
+    match url::Url::parse(url.trim()) {
+        Ok(mut parsed) => {
+            parsed.set_fragment(None);
+            parsed.to_string()
+        }
+        Err(_) => url.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str) -> Cache {
+        let dir = std::env::temp_dir().join(format!("bbcli-cache-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        Cache::with_dir(dir).unwrap()
+    }
+
+    fn story(link: &str) -> NewsStory {
+        NewsStory {
+            title: String::from("Title"),
+            description: String::from("Description"),
+            link: link.to_string(),
+            pub_date: String::from("2025-01-01 12:00:00"),
+            category: String::from("News"),
+            image_url: None,
+        }
+    }
+
+    #[test]
+    fn test_stats_counts_entries_by_kind() {
+        let cache = temp_cache("stats");
+        cache.save_feed("https://example.com/feed", &[story("https://example.com/1")]).unwrap();
+        cache.save_article("https://example.com/1", "Body").unwrap();
+        cache.save_article("https://example.com/2", "Body").unwrap();
+        fs::write(cache.cache_dir().join("unrelated.txt"), "ignored").unwrap();
+
+        let stats = cache.stats().unwrap();
+        assert_eq!(stats[0].0, EntryKind::Feed);
+        assert_eq!(stats[0].1.count, 1);
+        assert_eq!(stats[1].1.count, 2);
+        assert!(stats[1].1.total_bytes > 0);
+        assert!(stats[1].1.oldest <= stats[1].1.newest);
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn test_prune_by_size_removes_oldest_first() {
+        let cache = temp_cache("prune");
+        cache.save_article("https://example.com/old", &"x".repeat(1000)).unwrap();
+        let old_path = cache.article_cache_path("https://example.com/old");
+        let old_time = SystemTime::now() - Duration::from_secs(3600);
+        fs::File::options().write(true).open(&old_path).unwrap().set_modified(old_time).unwrap();
+        cache.save_article("https://example.com/new", &"x".repeat(1000)).unwrap();
+
+        let report = cache.prune(None, Some(1500)).unwrap();
+        assert_eq!(report.removed, 1);
+        assert!(!old_path.exists());
+        assert!(cache.load_article_offline("https://example.com/new").is_some());
+
+        // Nothing is older than a day
+        let report = cache.prune(Some(Duration::from_secs(86400)), None).unwrap();
+        assert_eq!(report.removed, 0);
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
+    }
+
+    fn install_fixture(cache: &Cache, name: &str, legacy_name: &str) -> PathBuf {
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(name);
+        let path = cache.cache_dir().join(legacy_name);
+        fs::copy(fixture, &path).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_cache_key_is_stable_and_normalised() {
+        // Pinned value: changing the key scheme orphans every user's cache
+        assert_eq!(
+            Cache::cache_key("https://feeds.bbci.co.uk/news/rss.xml"),
+            "8453e2a872e7cbe3f9941ca712374b22ef41e7c3b4c60d952f3755c461d2407b"
+        );
+        assert_eq!(
+            Cache::cache_key("HTTPS://Feeds.BBCI.co.uk:443/news/rss.xml#top"),
+            Cache::cache_key("https://feeds.bbci.co.uk/news/rss.xml")
+        );
+        assert_ne!(
+            Cache::cache_key("https://feeds.bbci.co.uk/news/world/rss.xml"),
+            Cache::cache_key("https://feeds.bbci.co.uk/news/rss.xml")
+        );
+    }
+
+    #[test]
+    fn test_round_trip_with_header() {
+        let cache = temp_cache("roundtrip");
+        cache.save_feed("https://example.com/feed", &[story("https://example.com/1")]).unwrap();
+
+        let data = fs::read(cache.feed_cache_path("https://example.com/feed")).unwrap();
+        assert_eq!(&data[..4], MAGIC);
+        assert_eq!(u32::from_le_bytes(data[4..8].try_into().unwrap()), FORMAT_VERSION);
+
+        let stories = cache.load_feed("https://example.com/feed").unwrap();
+        assert_eq!(stories[0].link, "https://example.com/1");
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn test_migrates_v1_fixtures() {
+        let cache = temp_cache("migrate");
+        let old_feed = install_fixture(&cache, "feed_v1.bin", "feed_5f2b1c7d9e8a4f36.bin");
+        let old_article = install_fixture(&cache, "article_v1.bin", "article_a1b2c3d4e5f60718.bin");
+
+        assert_eq!(cache.migrate().unwrap(), 2);
+        assert!(!old_feed.exists());
+        assert!(!old_article.exists());
+
+        let stories = cache.load_feed_offline("https://feeds.bbci.co.uk/news/technology/rss.xml").unwrap();
+        assert_eq!(stories.len(), 2);
+        assert_eq!(stories[0].title, "Chip maker posts record profits");
+        assert_eq!(stories[1].title, "Café owners welcome new rules");
+        assert_eq!(stories[1].image_url, None);
+        // Fixture timestamp is in the past, so it only loads offline
+        assert!(cache.load_feed("https://feeds.bbci.co.uk/news/technology/rss.xml").is_none());
+
+        let article = cache.load_article_offline("https://www.bbc.co.uk/news/articles/c0000000001o").unwrap();
+        assert!(article.starts_with("Chip maker posts record profits"));
+
+        // Already migrated
+        assert_eq!(cache.migrate().unwrap(), 0);
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn test_invalidates_other_versions_and_garbage() {
+        let cache = temp_cache("invalidate");
+        let url = "https://example.com/feed";
+        let path = cache.feed_cache_path(url);
+
+        // Future format version
+        let mut data = Vec::from(&MAGIC[..]);
+        data.extend_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+        fs::write(&path, &data).unwrap();
+        assert!(cache.load_feed_offline(url).is_none());
+        assert!(!path.exists());
+
+        // Headerless v1 data under a current key
+        let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/feed_v1.bin");
+        fs::copy(fixture, &path).unwrap();
+        assert!(cache.load_feed_offline(url).is_none());
+        assert!(!path.exists());
+
+        // Unmigratable legacy entry is removed
+        let garbage = cache.cache_dir().join("article_0123456789abcdef.bin");
+        fs::write(&garbage, b"not bincode").unwrap();
+        assert_eq!(cache.migrate().unwrap(), 0);
+        assert!(!garbage.exists());
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn test_torn_write_self_heals() {
+        let cache = temp_cache("torn");
+        let url = "https://example.com/article";
+        cache.save_article(url, &"Long article body. ".repeat(100)).unwrap();
+
+        // Simulate a crash halfway through a plain (non-atomic) write
+        let path = cache.article_cache_path(url);
+        let data = fs::read(&path).unwrap();
+        fs::write(&path, &data[..data.len() / 2]).unwrap();
+
+        assert!(cache.load_article_offline(url).is_none());
+        assert!(!path.exists(), "corrupt entry should be removed");
+
+        cache.save_article(url, "Fresh body").unwrap();
+        assert_eq!(cache.load_article_offline(url).unwrap(), "Fresh body");
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn test_atomic_write_leaves_no_temp_files_and_prune_removes_stale_ones() {
+        let cache = temp_cache("temp");
+        cache.save_feed("https://example.com/feed", &[story("https://example.com/1")]).unwrap();
+
+        let temp_files = || fs::read_dir(cache.cache_dir()).unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().ends_with(TEMP_SUFFIX))
+            .count();
+        assert_eq!(temp_files(), 0);
+
+        // Temp file from a writer that crashed before rename
+        let stale = cache.cache_dir().join(".feed_abc.bin.12345.tmp");
+        fs::write(&stale, b"partial").unwrap();
+        let old_time = SystemTime::now() - Duration::from_secs(STALE_TEMP_SECS * 2);
+        fs::File::options().write(true).open(&stale).unwrap().set_modified(old_time).unwrap();
+
+        cache.prune(None, None).unwrap();
+        assert_eq!(temp_files(), 0);
+        assert!(cache.load_feed_offline("https://example.com/feed").is_some());
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
+    }
+
+    #[test]
+    fn test_concurrent_writers_never_expose_partial_entries() {
+        let cache = temp_cache("concurrent");
+        let dir = cache.cache_dir().to_path_buf();
+        let url = "https://example.com/feed";
+        cache.save_feed(url, &[story("https://example.com/0")]).unwrap();
+
+        let writers: Vec<_> = (0..4).map(|w| {
+            let dir = dir.clone();
+            std::thread::spawn(move || {
+                let cache = Cache::with_dir(dir).unwrap();
+                for i in 0..25 {
+                    let stories: Vec<_> = (0..=(w * 25 + i) % 30)
+                        .map(|n| story(&format!("https://example.com/{}", n)))
+                        .collect();
+                    cache.save_feed(url, &stories).unwrap();
+                }
+            })
+        }).collect();
+
+        for _ in 0..200 {
+            assert!(cache.load_feed_offline(url).is_some(), "reader saw a missing or partial entry");
+        }
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let _ = fs::remove_dir_all(cache.cache_dir());
     }
 }
@@ -1,6 +1,8 @@
 use clap::{Parser, Subcommand};
-use anyhow::Result;
-use crate::{api, article_fetcher, date_utils, feeds};
+use anyhow::{Context, Result};
+use crate::{api, archive, article_fetcher, bookmarks, cache, config, date_utils, feeds, image_cache, session};
+use crate::keymap::Keymap;
+use std::io::Write;
 
 #[derive(Parser)]
 #[command(name = "bbcli")]
@@ -31,9 +33,146 @@ pub enum Commands {
         /// Article index (1-based)
         index: usize,
     },
+
+    /// Search the local archive of every story seen
+    Search {
+        /// Words to search for in titles, descriptions and article text
+        #[arg(required = true)]
+        query: Vec<String>,
+
+        /// Only stories seen since (e.g. 12h, 7d, 2w or 2025-01-31)
+        #[arg(long)]
+        since: Option<String>,
+    },
+
+    /// Download feeds, articles and images for offline reading
+    Sync {
+        /// Feeds to download, comma separated (default: all feeds)
+        #[arg(long, value_delimiter = ',')]
+        feeds: Vec<String>,
+
+        /// Articles to download per feed (default: all stories in the feed)
+        #[arg(long)]
+        articles: Option<usize>,
+
+        /// Also download story thumbnails
+        #[arg(long)]
+        images: bool,
+    },
+
+    /// Manage the on-disk cache
+    Cache {
+        #[command(subcommand)]
+        action: Option<CacheCommands>,
+    },
+
+    /// Manage saved stories (read-later list)
+    Saved {
+        #[command(subcommand)]
+        action: Option<SavedCommands>,
+    },
+
+    /// Inspect and create the config file
+    Config {
+        #[command(subcommand)]
+        action: Option<ConfigCommands>,
+    },
 }
 
-pub fn run_cli(cli: Cli) -> Result<()> {
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Print the config file location
+    Path,
+
+    /// Write a commented default config file
+    Init {
+        /// Overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Validate the config file and report problems
+    Check,
+
+    /// Save the theme and display toggles from the last session as config defaults
+    SaveDefaults,
+
+    /// Print the config file
+    Show {
+        /// Print the settings in use, with defaults filled in
+        #[arg(long)]
+        effective: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommands {
+    /// Show entry counts, sizes and ages
+    Stats,
+
+    /// Remove old entries (defaults to the limits in config)
+    Prune {
+        /// Remove entries older than this (e.g. 12h, 7d, 2w)
+        #[arg(long)]
+        max_age: Option<String>,
+
+        /// Shrink the cache below this size (e.g. 500K, 50M, 1G)
+        #[arg(long)]
+        max_size: Option<String>,
+    },
+
+    /// Remove all cached feeds and articles
+    Clear,
+
+    /// Print the cache directory
+    Path,
+}
+
+#[derive(Subcommand)]
+pub enum SavedCommands {
+    /// List saved stories
+    List,
+
+    /// Open saved story in browser by index
+    Open {
+        /// Saved story index (1-based)
+        index: usize,
+    },
+
+    /// Show saved article in terminal (works offline)
+    Show {
+        /// Saved story index (1-based)
+        index: usize,
+    },
+
+    /// Remove saved story by index
+    Rm {
+        /// Saved story index (1-based)
+        index: usize,
+    },
+}
+
+pub fn run_cli(cli: Cli, config: &config::Config) -> Result<()> {
+    // Saved stories don't depend on a feed
+    if let Some(Commands::Saved { action }) = cli.command {
+        return run_saved(action.unwrap_or(SavedCommands::List));
+    }
+
+    // Neither does cache management
+    if let Some(Commands::Cache { action }) = cli.command {
+        return run_cache(action.unwrap_or(CacheCommands::Stats), config);
+    }
+
+    // Sync selects its own feeds
+    if let Some(Commands::Sync { feeds, articles, images }) = cli.command {
+        return sync(&feeds, articles, images, &config.cache);
+    }
+
+    // Archive search covers all feeds unless --feed is given
+    if let Some(Commands::Search { query, since }) = cli.command {
+        return search_archive(&query.join(" "), since.as_deref(), cli.feed.as_deref());
+    }
+
     // Get feed URL
     let feed = if let Some(feed_name) = &cli.feed {
         feeds::get_feed_by_name(feed_name)?
@@ -45,6 +184,11 @@ pub fn run_cli(cli: Cli) -> Result<()> {
         Some(Commands::List) => list_headlines(&feed),
         Some(Commands::Open { index }) => open_article(&feed, index),
         Some(Commands::Show { index }) => show_article(&feed, index),
+        Some(Commands::Saved { .. })
+        | Some(Commands::Search { .. })
+        | Some(Commands::Cache { .. })
+        | Some(Commands::Sync { .. })
+        | Some(Commands::Config { .. }) => unreachable!("handled above"),
         None => {
             // No subcommand provided, default to listing
             list_headlines(&feed)
@@ -102,3 +246,419 @@ fn show_article(feed: &feeds::Feed, index: usize) -> Result<()> {
 
     Ok(())
 }
+
+fn search_archive(text: &str, since: Option<&str>, feed_name: Option<&str>) -> Result<()> {
+    let query = archive::ArchiveQuery {
+        text: text.to_string(),
+        since: since.map(archive::parse_since).transpose()?,
+        feed: feed_name.map(|name| feeds::get_feed_by_name(name).map(|f| f.name)).transpose()?,
+    };
+
+    let results = archive::Archive::open()?.search(&query)?;
+
+    if results.is_empty() {
+        println!("No archived stories match '{}'.", text);
+        return Ok(());
+    }
+
+    println!("# Archive: {}\n", text);
+
+    for (i, result) in results.iter().enumerate() {
+        let first_seen = chrono::DateTime::from_timestamp(result.first_seen, 0)
+            .map(|dt| date_utils::humanize_time(&dt.format("%Y-%m-%d %H:%M:%S").to_string()))
+            .unwrap_or_default();
+        println!("{}. {} ({}, first seen {})", i + 1, result.story.title, result.feeds.join(", "), first_seen);
+        println!("   {}", result.story.link);
+    }
+
+    Ok(())
+}
+
+fn sync(feed_names: &[String], article_limit: Option<usize>, images: bool, cache_config: &config::CacheConfig) -> Result<()> {
+    let feeds = if feed_names.is_empty() {
+        feeds::get_all_feeds()
+    } else {
+        feed_names.iter()
+            .map(|name| feeds::get_feed_by_name(name.trim()))
+            .collect::<Result<Vec<_>>>()?
+    };
+
+    let mut synced_feeds = 0;
+    let mut total = SyncCounts::default();
+
+    for (i, feed) in feeds.iter().enumerate() {
+        let prefix = format!("[{}/{}] {}", i + 1, feeds.len(), feed.name);
+        print_progress(&format!("{}: fetching feed...", prefix));
+
+        let stories = match api::refresh_stories(&feed.url) {
+            Ok(stories) => stories,
+            Err(e) => {
+                println!("\r{}: failed ({:#})\x1b[K", prefix, e);
+                total.failed += 1;
+                continue;
+            }
+        };
+
+        let selected = &stories[..article_limit.unwrap_or(stories.len()).min(stories.len())];
+        let mut counts = SyncCounts::default();
+
+        for (n, story) in selected.iter().enumerate() {
+            print_progress(&format!("{}: {} stories, article {}/{}", prefix, stories.len(), n + 1, selected.len()));
+            counts.add_article(&article_fetcher::fetch_article(&story.link));
+
+            if images {
+                if let Some(ref image_url) = story.image_url {
+                    match image_cache::download_image_bytes(image_url) {
+                        Ok(_) => counts.images += 1,
+                        Err(_) => counts.failed += 1,
+                    }
+                }
+            }
+        }
+
+        println!("\r{}: {} stories, {}\x1b[K", prefix, stories.len(), counts.summary(images));
+        synced_feeds += 1;
+        total.add(&counts);
+    }
+
+    if synced_feeds == 0 {
+        anyhow::bail!("Sync failed: no feeds could be downloaded");
+    }
+
+    // Stories saved before their article was downloaded. An out of date copy is still
+    // better than none for these.
+    let mut saved = bookmarks::Bookmarks::load();
+    let missing = saved.missing_articles();
+    let mut counts = SyncCounts::default();
+    for (n, link) in missing.iter().enumerate() {
+        print_progress(&format!("Saved stories: article {}/{}", n + 1, missing.len()));
+        let fetched = article_fetcher::fetch_article(link);
+        if let Ok((ref article, _)) = fetched {
+            saved.fill_article(link, article)?;
+        }
+        counts.add_article(&fetched);
+    }
+    if !missing.is_empty() {
+        println!("\rSaved stories: {}\x1b[K", counts.summary(false));
+        total.add(&counts);
+    }
+
+    println!(
+        "\nOffline edition ready: {} feeds, {} ({} failed).",
+        synced_feeds, total.summary(images), total.failed
+    );
+
+    if let Some(warning) = cache_limit_warning(&cache::Cache::new()?, cache_config.max_bytes()?)? {
+        println!("{}", warning);
+    }
+
+    Ok(())
+}
+
+/// What a sync fetched. Articles that couldn't be downloaded but had an out of date copy in
+/// the cache or archive are counted separately, so the report doesn't overstate what's new.
+#[derive(Debug, Default, PartialEq)]
+struct SyncCounts {
+    articles: usize,
+    stale: usize,
+    images: usize,
+    failed: usize,
+}
+
+impl SyncCounts {
+    fn add_article(&mut self, fetched: &Result<(String, article_fetcher::ArticleSource)>) {
+        match fetched {
+            Ok((_, article_fetcher::ArticleSource::Stale)) => self.stale += 1,
+            Ok(_) => self.articles += 1,
+            Err(_) => self.failed += 1,
+        }
+    }
+
+    fn add(&mut self, other: &SyncCounts) {
+        self.articles += other.articles;
+        self.stale += other.stale;
+        self.images += other.images;
+        self.failed += other.failed;
+    }
+
+    fn summary(&self, images: bool) -> String {
+        let mut summary = format!("{} articles", self.articles);
+        if self.stale > 0 {
+            summary.push_str(&format!(", {} out of date", self.stale));
+        }
+        if images {
+            summary.push_str(&format!(", {} images", self.images));
+        }
+        summary
+    }
+}
+
+// The size limit is applied at every start, oldest entries first, so an edition bigger than
+// it would be partly removed before it's read
+fn cache_limit_warning(cache: &cache::Cache, max_bytes: Option<u64>) -> Result<Option<String>> {
+    let Some(max_bytes) = max_bytes else {
+        return Ok(None);
+    };
+    let total: u64 = cache.stats()?.iter().map(|(_, stats)| stats.total_bytes).sum();
+    Ok((total > max_bytes).then(|| format!(
+        "Warning: the cache is {}, over its {} limit, so the oldest entries (possibly from this edition) \
+         will be removed the next time bbcli starts. Raise max_size_mb under [cache] in the config to keep them.",
+        format_size(total),
+        format_size(max_bytes)
+    )))
+}
+
+// Overwrite the current terminal line with a progress message
+fn print_progress(message: &str) {
+    print!("\r{}\x1b[K", message);
+    let _ = std::io::stdout().flush();
+}
+
+/// Config commands run before the config is loaded, so they work on a broken file
+pub fn run_config(action: ConfigCommands) -> Result<()> {
+    match action {
+        ConfigCommands::Path => {
+            println!("{}", config::get_config_path()?.display());
+        }
+        ConfigCommands::Init { force } => {
+            let path = config::get_config_path()?;
+            if path.exists() && !force {
+                anyhow::bail!("{} already exists. Use --force to overwrite it.", path.display());
+            }
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, config::DEFAULT_CONFIG)?;
+            println!("Wrote default config to {}", path.display());
+        }
+        ConfigCommands::Check => {
+            let loaded = config::load_config()?;
+            if !loaded.exists {
+                println!("No config file at {}, using defaults.", loaded.path.display());
+                return Ok(());
+            }
+            for warning in &loaded.warnings {
+                println!("warning: {}", warning);
+            }
+            match loaded.warnings.len() {
+                0 => println!("{} is valid.", loaded.path.display()),
+                n => println!("{} is valid with {} warning{}.", loaded.path.display(), n, if n == 1 { "" } else { "s" }),
+            }
+        }
+        ConfigCommands::SaveDefaults => {
+            let session = session::Session::load()
+                .context("No saved session yet. Run bbcli, set the toggles you want, then quit.")?;
+            let path = config::get_config_path()?;
+            let content = if path.exists() {
+                std::fs::read_to_string(&path)?
+            } else {
+                config::DEFAULT_CONFIG.to_string()
+            };
+            let updated = session.write_to_config(&content)?;
+            // Don't write a file that bbcli itself would refuse to load
+            config::parse_config(&updated)?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            cache::write_atomic(&path, updated.as_bytes())?;
+            println!("Saved theme and display settings to {}", path.display());
+        }
+        ConfigCommands::Show { effective: true } => {
+            let mut config = config::load_config()?.config;
+            // List every binding, not just the ones the file changes
+            config.keybindings = Keymap::from_bindings(&config.keybindings).to_bindings();
+            print!("{}", toml::to_string_pretty(&config)?);
+        }
+        ConfigCommands::Show { effective: false } => {
+            let path = config::get_config_path()?;
+            if !path.exists() {
+                anyhow::bail!("No config file at {}. Use --effective to see the defaults.", path.display());
+            }
+            print!("{}", std::fs::read_to_string(&path)?);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_cache(action: CacheCommands, config: &config::Config) -> Result<()> {
+    let cache = cache::Cache::new()?;
+
+    match action {
+        CacheCommands::Stats => {
+            println!("# Cache ({})\n", cache.cache_dir().display());
+
+            let stats = cache.stats()?;
+            for (kind, kind_stats) in &stats {
+                println!("{}: {} entries, {}", kind.name(), kind_stats.count, format_size(kind_stats.total_bytes));
+                if let (Some(oldest), Some(newest)) = (kind_stats.oldest, kind_stats.newest) {
+                    println!("  oldest: {}", humanize_system_time(oldest));
+                    println!("  newest: {}", humanize_system_time(newest));
+                }
+            }
+
+            let total: u64 = stats.iter().map(|(_, s)| s.total_bytes).sum();
+            println!("\nTotal: {}", format_size(total));
+        }
+        CacheCommands::Prune { max_age, max_size } => {
+            let max_age = match max_age {
+                Some(value) => Some(date_utils::parse_duration(&value)?),
+                None => config.cache.max_age()?,
+            };
+            let max_bytes = match max_size {
+                Some(value) => Some(parse_size(&value)?),
+                None => config.cache.max_bytes()?,
+            };
+
+            let report = cache.prune(max_age, max_bytes)?;
+            println!("Removed {} entries, freed {}.", report.removed, format_size(report.freed_bytes));
+        }
+        CacheCommands::Clear => {
+            cache.clear_all()?;
+            println!("Cache cleared.");
+        }
+        CacheCommands::Path => {
+            println!("{}", cache.cache_dir().display());
+        }
+    }
+
+    Ok(())
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+// Parse sizes like "500K", "50M", "1G" or a plain number of bytes
+fn parse_size(value: &str) -> Result<u64> {
+    let value = value.trim().to_uppercase();
+    let value = value.trim_end_matches('B');
+    let (number, multiplier) = match value.chars().last() {
+        Some('K') => (&value[..value.len() - 1], 1024),
+        Some('M') => (&value[..value.len() - 1], 1024 * 1024),
+        Some('G') => (&value[..value.len() - 1], 1024 * 1024 * 1024),
+        _ => (value, 1),
+    };
+    let invalid = || anyhow::anyhow!("Invalid size: '{}'. Use e.g. 500K, 50M or 1G", value);
+    let number: u64 = number.trim().parse().map_err(|_| invalid())?;
+    number.checked_mul(multiplier).ok_or_else(invalid)
+}
+
+fn humanize_system_time(time: std::time::SystemTime) -> String {
+    let datetime: chrono::DateTime<chrono::Utc> = time.into();
+    date_utils::humanize_time(&datetime.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+fn run_saved(action: SavedCommands) -> Result<()> {
+    let mut saved = bookmarks::Bookmarks::load();
+
+    match action {
+        SavedCommands::List => {
+            if saved.is_empty() {
+                println!("No saved stories. Press 'b' in the TUI to save one.");
+                return Ok(());
+            }
+
+            println!("# Saved\n");
+
+            for (i, bookmark) in saved.items().iter().enumerate() {
+                let humanized_date = date_utils::humanize_time(&bookmark.story.pub_date);
+                println!("{}. {} ({}, {})", i + 1, bookmark.story.title, bookmark.feed_name, humanized_date);
+            }
+        }
+        SavedCommands::Open { index } => {
+            let bookmark = get_bookmark(&saved, index)?;
+            println!("Opening: {}", bookmark.story.title);
+            webbrowser::open(&bookmark.story.link)?;
+            println!("Launched in browser.");
+        }
+        SavedCommands::Show { index } => {
+            let bookmark = get_bookmark(&saved, index)?;
+            // Prefer the stored article body, fetch if it was never saved
+            let article_text = match bookmark.article {
+                Some(ref content) => content.clone(),
+                None => article_fetcher::fetch_article_content(&bookmark.story.link)?,
+            };
+            println!("{}", article_text);
+        }
+        SavedCommands::Rm { index } => {
+            let link = get_bookmark(&saved, index)?.story.link.clone();
+            if let Some(removed) = saved.remove(&link)? {
+                println!("Removed: {}", removed.story.title);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn get_bookmark(saved: &bookmarks::Bookmarks, index: usize) -> Result<&bookmarks::Bookmark> {
+    if index == 0 || index > saved.len() {
+        anyhow::bail!("Invalid saved story index: {}. Available: 1-{}", index, saved.len());
+    }
+    Ok(&saved.items()[index - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("500K").unwrap(), 500 * 1024);
+        assert_eq!(parse_size("50mb").unwrap(), 50 * 1024 * 1024);
+        assert_eq!(parse_size("1G").unwrap(), 1024 * 1024 * 1024);
+        assert_eq!(parse_size("123").unwrap(), 123);
+        assert!(parse_size("lots").is_err());
+        // Too large to represent rather than wrapping around
+        assert!(parse_size("99999999999G").unwrap_err().to_string().starts_with("Invalid size"));
+    }
+
+    #[test]
+    fn test_sync_counts_out_of_date_articles_separately() {
+        use article_fetcher::ArticleSource;
+
+        let mut counts = SyncCounts::default();
+        counts.add_article(&Ok((String::new(), ArticleSource::Network)));
+        counts.add_article(&Ok((String::new(), ArticleSource::Cache)));
+        counts.add_article(&Ok((String::new(), ArticleSource::Stale)));
+        counts.add_article(&Err(anyhow::anyhow!("offline")));
+        assert_eq!(counts, SyncCounts { articles: 2, stale: 1, images: 0, failed: 1 });
+        assert_eq!(counts.summary(false), "2 articles, 1 out of date");
+
+        let mut total = SyncCounts { images: 3, ..SyncCounts::default() };
+        total.add(&counts);
+        assert_eq!(total.summary(true), "2 articles, 1 out of date, 3 images");
+    }
+
+    #[test]
+    fn test_sync_warns_when_edition_exceeds_cache_limit() {
+        let dir = std::env::temp_dir().join(format!("bbcli-sync-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let cache = cache::Cache::with_dir(dir.clone()).unwrap();
+        for i in 0..3 {
+            cache.save_article(&format!("https://www.bbc.co.uk/news/{}", i), &"x".repeat(1000)).unwrap();
+        }
+
+        assert_eq!(cache_limit_warning(&cache, None).unwrap(), None);
+        assert_eq!(cache_limit_warning(&cache, Some(1024 * 1024)).unwrap(), None);
+        let warning = cache_limit_warning(&cache, Some(2000)).unwrap().unwrap();
+        assert!(warning.contains("max_size_mb"), "{}", warning);
+
+        // The warning is right: the next start's prune removes part of the edition
+        assert!(cache.prune(None, Some(2000)).unwrap().removed > 0);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,158 @@
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+
+/// How many colours the terminal can show. Theme colours are mapped down to the nearest
+/// ones it has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColorDepth {
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,       // Detect from NO_COLOR, COLORTERM and TERM
+    #[serde(rename = "truecolor")]
+    TrueColor,  // 24-bit colour, themes as written
+    #[serde(rename = "256")]
+    Ansi256,    // xterm 256-colour palette
+    #[serde(rename = "16")]
+    Ansi16,     // The 16 basic colours, like the Linux console
+    #[serde(rename = "mono")]
+    Monochrome, // No colour, only bold, reverse and underline
+}
+
+impl ColorDepth {
+    /// The depth to use. NO_COLOR wins over config; otherwise "auto" looks at the terminal.
+    pub fn detect(self) -> Self {
+        self.detect_from(|var| std::env::var(var).ok())
+    }
+
+    fn detect_from(self, env: impl Fn(&str) -> Option<String>) -> Self {
+        // https://no-color.org: any non-empty value
+        if env("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            return ColorDepth::Monochrome;
+        }
+        if self != ColorDepth::Auto {
+            return self;
+        }
+
+        if env("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit") {
+            return ColorDepth::TrueColor;
+        }
+        match env("TERM").as_deref() {
+            // Windows terminals don't set TERM and all handle 24-bit colour
+            None | Some("") => ColorDepth::TrueColor,
+            Some("dumb") => ColorDepth::Monochrome,
+            Some(term) if term.contains("256color") => ColorDepth::Ansi256,
+            Some(term) if term.contains("truecolor") || term.contains("direct") => ColorDepth::TrueColor,
+            Some(_) => ColorDepth::Ansi16,
+        }
+    }
+
+    /// The nearest colour this depth can show
+    pub fn reduce(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorDepth::Monochrome, _) => Color::Reset,
+            (ColorDepth::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_256((r, g, b))),
+            (ColorDepth::Ansi16, Color::Rgb(r, g, b)) => nearest_16((r, g, b)),
+            (ColorDepth::Ansi16, Color::Indexed(index)) => nearest_16(index_to_rgb(index)),
+            _ => color,
+        }
+    }
+}
+
+type Rgb = (u8, u8, u8);
+
+// Levels of each channel in the 6x6x6 colour cube (indexes 16-231)
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+// The basic colours with xterm's default values
+const ANSI_16: [(Color, Rgb); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn distance(a: Rgb, b: Rgb) -> u32 {
+    let d = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    d(a.0, b.0) + d(a.1, b.1) + d(a.2, b.2)
+}
+
+fn nearest_level(value: u8) -> usize {
+    (0..CUBE_LEVELS.len())
+        .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+        .unwrap_or(0)
+}
+
+// Closest of the colour cube entry and the grey ramp entry
+fn nearest_256(rgb: Rgb) -> u8 {
+    let (r, g, b) = (nearest_level(rgb.0), nearest_level(rgb.1), nearest_level(rgb.2));
+    let cube = (16 + 36 * r + 6 * g + b) as u8;
+
+    let average = (rgb.0 as u32 + rgb.1 as u32 + rgb.2 as u32) / 3;
+    let grey = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+
+    if distance(rgb, index_to_rgb(grey)) < distance(rgb, index_to_rgb(cube)) { grey } else { cube }
+}
+
+fn nearest_16(rgb: Rgb) -> Color {
+    ANSI_16.iter()
+        .min_by_key(|(_, value)| distance(rgb, *value))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+fn index_to_rgb(index: u8) -> Rgb {
+    match index {
+        0..=15 => ANSI_16[index as usize].1,
+        16..=231 => {
+            let i = (index - 16) as usize;
+            (CUBE_LEVELS[i / 36], CUBE_LEVELS[i / 6 % 6], CUBE_LEVELS[i % 6])
+        }
+        _ => {
+            let level = 8 + 10 * (index - 232);
+            (level, level, level)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(configured: ColorDepth, vars: &[(&str, &str)]) -> ColorDepth {
+        configured.detect_from(|var| vars.iter().find(|(name, _)| *name == var).map(|(_, value)| value.to_string()))
+    }
+
+    #[test]
+    fn test_detects_depth_from_environment() {
+        assert_eq!(detect(ColorDepth::Auto, &[("COLORTERM", "truecolor"), ("TERM", "xterm-256color")]), ColorDepth::TrueColor);
+        assert_eq!(detect(ColorDepth::Auto, &[("TERM", "tmux-256color")]), ColorDepth::Ansi256);
+        assert_eq!(detect(ColorDepth::Auto, &[("TERM", "linux")]), ColorDepth::Ansi16);
+        assert_eq!(detect(ColorDepth::Ansi256, &[("TERM", "linux")]), ColorDepth::Ansi256);
+        assert_eq!(detect(ColorDepth::TrueColor, &[("NO_COLOR", "1")]), ColorDepth::Monochrome);
+        assert_eq!(detect(ColorDepth::Ansi16, &[("NO_COLOR", "")]), ColorDepth::Ansi16);
+    }
+
+    #[test]
+    fn test_reduces_to_nearest_colour() {
+        // BBC red, off-white and the light theme's grey
+        assert_eq!(ColorDepth::Ansi256.reduce(Color::Rgb(234, 68, 57)), Color::Indexed(167));
+        assert_eq!(ColorDepth::Ansi256.reduce(Color::Rgb(255, 254, 252)), Color::Indexed(231));
+        assert_eq!(ColorDepth::Ansi256.reduce(Color::Rgb(148, 148, 148)), Color::Indexed(246));
+        assert_eq!(ColorDepth::Ansi16.reduce(Color::Rgb(234, 68, 57)), Color::LightRed);
+        assert_eq!(ColorDepth::Ansi16.reduce(Color::Rgb(148, 148, 148)), Color::DarkGray);
+        assert_eq!(ColorDepth::Ansi16.reduce(Color::Indexed(231)), Color::White);
+        assert_eq!(ColorDepth::TrueColor.reduce(Color::Rgb(1, 2, 3)), Color::Rgb(1, 2, 3));
+        assert_eq!(ColorDepth::Monochrome.reduce(Color::Red), Color::Reset);
+    }
+}
@@ -1,102 +1,565 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use crate::theme::ThemeName;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use crate::app::{ImageProtocol, LayoutMode, PreviewPosition, SortOrder};
+use crate::keymap::{KeyMode, Keymap, Keys};
+use crate::color_depth::ColorDepth;
+use crate::theme::{load_themes, Theme, ThemeName, ThemeSpec};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, Deserialize, Serialize)]
 pub struct Config {
     #[serde(default)]
     pub keybindings: KeyBindings,
     #[serde(default)]
     pub theme: ThemeName,
+    /// Colours the terminal can show: "auto", "truecolor", "256", "16" or "mono"
+    #[serde(default)]
+    pub color_depth: ColorDepth,
+    #[serde(default)]
+    pub cache: CacheConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub input: InputConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub reader: ReaderConfig,
+    /// User themes by name, on top of the built-ins and theme files
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub themes: BTreeMap<String, ThemeSpec>,
+}
+
+/// Startup values for the settings that can also be toggled at runtime
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct DisplayConfig {
+    /// Story order: "default" (feed order), "newest" or "oldest"
+    #[serde(default)]
+    pub sort: SortOrder,
+    /// Show dates as "2 hours ago" rather than a timestamp
+    #[serde(default = "default_humanize_dates")]
+    pub humanize_dates: bool,
+    /// Image protocol: "auto", "halfblocks", "sixel" or "kitty"
+    #[serde(default)]
+    pub image_protocol: ImageProtocol,
+    /// Show the preview pane
+    #[serde(default)]
+    pub preview: bool,
+}
+
+fn default_humanize_dates() -> bool { true }
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            sort: SortOrder::default(),
+            humanize_dates: default_humanize_dates(),
+            image_protocol: ImageProtocol::default(),
+            preview: false,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-pub struct KeyBindings {
-    #[serde(default = "default_quit")]
-    pub quit: char,
-    #[serde(default = "default_open")]
-    pub open: char,
-    #[serde(default = "default_open_new_tab")]
-    pub open_new_tab: char,
-    #[serde(default = "default_refresh")]
-    pub refresh: char,
-    #[serde(default = "default_latest")]
-    pub latest: char,
-    #[serde(default = "default_scroll_up")]
-    pub scroll_up: char,
-    #[serde(default = "default_scroll_down")]
-    pub scroll_down: char,
-    #[serde(default = "default_scroll_bottom")]
-    pub scroll_bottom: char,
-}
-
-fn default_quit() -> char { 'q' }
-fn default_open() -> char { 'o' }
-fn default_open_new_tab() -> char { 'O' }
-fn default_refresh() -> char { 'r' }
-fn default_latest() -> char { 'l' }
-fn default_scroll_up() -> char { 'k' }
-fn default_scroll_down() -> char { 'j' }
-fn default_scroll_bottom() -> char { 'G' }
+pub struct SessionConfig {
+    /// Restore toggles, feed, selection and scroll position from the last run
+    #[serde(default = "default_session_restore")]
+    pub restore: bool,
+}
 
-impl Default for KeyBindings {
+fn default_session_restore() -> bool { true }
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self { restore: default_session_restore() }
+    }
+}
+
+/// Handling of held keys and bursts of buffered input
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct InputConfig {
+    /// Presses of the same key closer together than this are treated as key repeat
+    #[serde(default = "default_input_repeat_ms")]
+    pub repeat_ms: u64,
+    /// How long after a burst of repeats the input still counts as unsettled
+    #[serde(default = "default_input_settle_ms")]
+    pub settle_ms: u64,
+    /// Ask before opening a story in the browser while the input is unsettled
+    #[serde(default = "default_input_confirm_open")]
+    pub confirm_open: bool,
+    /// Capture the mouse. Turn off to select text with the mouse as usual.
+    #[serde(default = "default_input_mouse")]
+    pub mouse: bool,
+    /// Two clicks on the same thing within this make a double-click
+    #[serde(default = "default_input_double_click_ms")]
+    pub double_click_ms: u64,
+}
+
+fn default_input_repeat_ms() -> u64 { 50 }
+fn default_input_settle_ms() -> u64 { 500 }
+fn default_input_confirm_open() -> bool { true }
+fn default_input_mouse() -> bool { true }
+fn default_input_double_click_ms() -> u64 { 400 }
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self {
+            repeat_ms: default_input_repeat_ms(),
+            settle_ms: default_input_settle_ms(),
+            confirm_open: default_input_confirm_open(),
+            mouse: default_input_mouse(),
+            double_click_ms: default_input_double_click_ms(),
+        }
+    }
+}
+
+/// Arrangement of the feeds, story list and reading pane
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct LayoutConfig {
+    /// "compact" (story list with optional preview) or "panes" (feed sidebar, list and reading pane)
+    #[serde(default)]
+    pub mode: LayoutMode,
+    /// Width of the feed sidebar in columns
+    #[serde(default = "default_layout_sidebar_width")]
+    pub sidebar_width: u16,
+    /// Share of the width beside the sidebar given to the story list, in percent
+    #[serde(default = "default_layout_list_percent")]
+    pub list_percent: u16,
+    /// Share of the content area given to the preview, in percent (compact)
+    #[serde(default = "default_layout_preview_percent")]
+    pub preview_percent: u16,
+    /// Where the preview or reading pane goes: "right" of the story list or at the "bottom"
+    #[serde(default)]
+    pub preview_position: PreviewPosition,
+    /// Share of the preview given to the image: its height, or its width when the preview is at the bottom
+    #[serde(default = "default_layout_image_percent")]
+    pub image_percent: u16,
+    /// Terminals narrower than this many columns put the preview at the bottom (0 = never)
+    #[serde(default = "default_layout_stack_below")]
+    pub stack_below: u16,
+}
+
+fn default_layout_sidebar_width() -> u16 { 26 }
+fn default_layout_list_percent() -> u16 { 45 }
+fn default_layout_preview_percent() -> u16 { 20 }
+fn default_layout_image_percent() -> u16 { 40 }
+fn default_layout_stack_below() -> u16 { 100 }
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            mode: LayoutMode::default(),
+            sidebar_width: default_layout_sidebar_width(),
+            list_percent: default_layout_list_percent(),
+            preview_percent: default_layout_preview_percent(),
+            preview_position: PreviewPosition::default(),
+            image_percent: default_layout_image_percent(),
+            stack_below: default_layout_stack_below(),
+        }
+    }
+}
+
+/// How the full article is laid out for reading
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ReaderConfig {
+    /// Widest line in columns, centred in the pane (0 = use the full width)
+    #[serde(default = "default_reader_max_width")]
+    pub max_width: u16,
+    /// Spread words out so lines end flush with the right margin
+    #[serde(default)]
+    pub justify: bool,
+    /// Blank lines between paragraphs
+    #[serde(default = "default_reader_paragraph_spacing")]
+    pub paragraph_spacing: usize,
+    /// Wrap quotes and list items under their text, leaving the `>` or bullet hanging
+    #[serde(default = "default_reader_hanging_indent")]
+    pub hanging_indent: bool,
+}
+
+fn default_reader_max_width() -> u16 { 80 }
+fn default_reader_paragraph_spacing() -> usize { 1 }
+fn default_reader_hanging_indent() -> bool { true }
+
+impl Default for ReaderConfig {
     fn default() -> Self {
         Self {
-            quit: default_quit(),
-            open: default_open(),
-            open_new_tab: default_open_new_tab(),
-            refresh: default_refresh(),
-            latest: default_latest(),
-            scroll_up: default_scroll_up(),
-            scroll_down: default_scroll_down(),
-            scroll_bottom: default_scroll_bottom(),
+            max_width: default_reader_max_width(),
+            justify: false,
+            paragraph_spacing: default_reader_paragraph_spacing(),
+            hanging_indent: default_reader_hanging_indent(),
         }
     }
 }
 
-impl Default for Config {
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CacheConfig {
+    /// Remove cache entries older than this many days (0 = no limit)
+    #[serde(default = "default_cache_max_age_days")]
+    pub max_age_days: u64,
+    /// Keep the cache directory under this many megabytes (0 = no limit)
+    #[serde(default = "default_cache_max_size_mb")]
+    pub max_size_mb: u64,
+}
+
+fn default_cache_max_age_days() -> u64 { 30 }
+fn default_cache_max_size_mb() -> u64 { 100 }
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            max_age_days: default_cache_max_age_days(),
+            max_size_mb: default_cache_max_size_mb(),
+        }
+    }
+}
+
+impl CacheConfig {
+    pub fn max_age(&self) -> Result<Option<std::time::Duration>> {
+        if self.max_age_days == 0 {
+            return Ok(None);
+        }
+        let seconds = self.max_age_days.checked_mul(24 * 60 * 60)
+            .ok_or_else(|| anyhow::anyhow!("Invalid duration: cache.max_age_days = {} is too large", self.max_age_days))?;
+        Ok(Some(std::time::Duration::from_secs(seconds)))
+    }
+
+    pub fn max_bytes(&self) -> Result<Option<u64>> {
+        if self.max_size_mb == 0 {
+            return Ok(None);
+        }
+        let bytes = self.max_size_mb.checked_mul(1024 * 1024)
+            .ok_or_else(|| anyhow::anyhow!("Invalid size: cache.max_size_mb = {} is too large", self.max_size_mb))?;
+        Ok(Some(bytes))
+    }
+}
+
+/// Key bindings per mode, keyed by action name. Bindings for the story list sit directly in
+/// [keybindings]; the other modes have their own tables. Unlisted actions keep their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyBindings {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub feed_menu: BTreeMap<String, Keys>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub article: BTreeMap<String, Keys>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub help: BTreeMap<String, Keys>,
+    #[serde(flatten)]
+    pub normal: BTreeMap<String, Keys>,
+}
+
+impl Default for KeyBindings {
     fn default() -> Self {
+        let mut bindings = Self::empty();
+        for mode in KeyMode::ALL {
+            let table = bindings.mode_mut(mode);
+            for (action, keys) in mode.default_keys() {
+                table.insert(action.name().to_string(), keys);
+            }
+        }
+        bindings
+    }
+}
+
+// Written by hand rather than with #[serde(flatten)], which buffers the table and loses the
+// line and column of bad values
+impl<'de> Deserialize<'de> for KeyBindings {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = KeyBindings;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a table of key bindings")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<KeyBindings, A::Error> {
+                let mut bindings = KeyBindings::empty();
+                while let Some(name) = map.next_key::<String>()? {
+                    match KeyMode::ALL.into_iter().find(|mode| mode.table() == Some(name.as_str())) {
+                        Some(mode) => *bindings.mode_mut(mode) = map.next_value()?,
+                        None => {
+                            bindings.normal.insert(name, map.next_value()?);
+                        }
+                    }
+                }
+                Ok(bindings)
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+impl KeyBindings {
+    /// No bindings configured in any mode
+    pub fn empty() -> Self {
         Self {
-            keybindings: KeyBindings::default(),
-            theme: ThemeName::default(),
+            feed_menu: BTreeMap::new(),
+            article: BTreeMap::new(),
+            help: BTreeMap::new(),
+            normal: BTreeMap::new(),
+        }
+    }
+
+    pub fn mode(&self, mode: KeyMode) -> &BTreeMap<String, Keys> {
+        match mode {
+            KeyMode::Normal => &self.normal,
+            KeyMode::FeedMenu => &self.feed_menu,
+            KeyMode::Article => &self.article,
+            KeyMode::Help => &self.help,
         }
     }
+
+    pub fn mode_mut(&mut self, mode: KeyMode) -> &mut BTreeMap<String, Keys> {
+        match mode {
+            KeyMode::Normal => &mut self.normal,
+            KeyMode::FeedMenu => &mut self.feed_menu,
+            KeyMode::Article => &mut self.article,
+            KeyMode::Help => &mut self.help,
+        }
+    }
+
+    /// Config paths of bindings for actions that don't exist in their mode
+    fn unknown_actions(&self) -> Vec<String> {
+        KeyMode::ALL
+            .into_iter()
+            .flat_map(|mode| {
+                self.mode(mode)
+                    .keys()
+                    .filter(move |name| mode.action(name).is_none())
+                    .map(move |name| match mode.table() {
+                        Some(table) => format!("keybindings.{}.{}", table, name),
+                        None => format!("keybindings.{}", name),
+                    })
+            })
+            .collect()
+    }
 }
 
-pub fn load_config() -> Result<Config> {
-    let config_path = get_config_path()?;
+/// Commented default config, written by `bbcli config init`
+pub const DEFAULT_CONFIG: &str = include_str!("../example-config.toml");
+
+/// A config file that parsed, and anything suspicious found in it
+pub struct LoadedConfig {
+    pub config: Config,
+    pub path: PathBuf,
+    pub exists: bool,
+    pub warnings: Vec<String>,
+    pub themes: Vec<Theme>,  // Every theme that can be picked
+}
+
+/// Load the config file, or defaults if there isn't one.
+/// Syntax errors and invalid values are errors; unknown keys and conflicting bindings are warnings.
+pub fn load_config() -> Result<LoadedConfig> {
+    let path = get_config_path()?;
+    let exists = path.exists();
+
+    let content = if exists {
+        std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?
+    } else {
+        String::new()
+    };
 
-    if !config_path.exists() {
-        return Ok(Config::default());
+    let (config, mut warnings) = parse_config(&content)
+        .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+    let (themes, theme_warnings) = load_themes(&config.themes, crate::paths::themes_dir().ok().as_deref());
+    warnings.extend(theme_warnings);
+    warnings.extend(check_theme(&content, &config.theme, &themes));
+
+    Ok(LoadedConfig { config, path, exists, warnings, themes })
+}
+
+// A theme that doesn't exist falls back to light rather than refusing to start, as it may
+// come from a theme file that was removed
+fn check_theme(content: &str, name: &ThemeName, themes: &[Theme]) -> Option<String> {
+    if themes.iter().any(|theme| theme.name == name.0) {
+        return None;
     }
+    let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+    let warning = format!("unknown theme `{}`, using light. Available: {}", name, names.join(", "));
+    Some(match find_key_line(content, "theme") {
+        Some(line) => format!("line {}: {}", line, warning),
+        None => warning,
+    })
+}
+
+/// Parse config TOML, returning the config and any warnings
+pub fn parse_config(content: &str) -> Result<(Config, Vec<String>)> {
+    let mut unknown_keys = Vec::new();
+    let deserializer = toml::Deserializer::new(content);
+    // Errors from toml carry the line and column of the problem
+    let config: Config = serde_ignored::deserialize(deserializer, |path| unknown_keys.push(path.to_string()))
+        .map_err(|e| anyhow::anyhow!("{}", e.to_string().trim_end()))?;
+
+    // Key bindings are free-form maps, so serde can't spot unknown action names
+    unknown_keys.extend(config.keybindings.unknown_actions());
+
+    let mut unknown_keys: Vec<(Option<usize>, String)> = unknown_keys
+        .into_iter()
+        .map(|key| (find_key_line(content, &key), key))
+        .collect();
+    unknown_keys.sort_by_key(|(line, _)| line.unwrap_or(usize::MAX));
 
-    let content = std::fs::read_to_string(&config_path)
-        .context("Failed to read config file")?;
+    let mut warnings: Vec<String> = unknown_keys
+        .iter()
+        .map(|(line, key)| match line {
+            Some(line) => format!("line {}: unknown key `{}`", line, key),
+            None => format!("unknown key `{}`", key),
+        })
+        .collect();
 
-    let config: Config = toml::from_str(&content)
-        .context("Failed to parse config file")?;
+    warnings.extend(Keymap::from_bindings(&config.keybindings).conflicts());
 
-    Ok(config)
+    // Limits too large to work with are refused here, not when the cache is pruned
+    config.cache.max_age()?;
+    config.cache.max_bytes()?;
+
+    Ok((config, warnings))
+}
+
+// Best effort line number (1-based) of a dotted key such as "keybindings.qiut"
+pub(crate) fn find_key_line(content: &str, key_path: &str) -> Option<usize> {
+    let (table, key) = key_path.rsplit_once('.').unwrap_or(("", key_path));
+    let mut current_table = String::new();
+
+    for (number, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            current_table = header.trim().to_string();
+            // An unknown table is reported at its header
+            if current_table == key_path {
+                return Some(number + 1);
+            }
+            continue;
+        }
+        let line_key = line.split('=').next().unwrap_or("").trim().trim_matches('"');
+        if current_table == table && line_key == key {
+            return Some(number + 1);
+        }
+    }
+
+    None
+}
+
+/// Notices changes to the config file by polling its modification time and size
+pub struct ConfigWatcher {
+    path: PathBuf,
+    stamp: Option<(SystemTime, u64)>,
 }
 
-fn get_config_path() -> Result<PathBuf> {
-    // Try ~/.bbcli first
-    if let Some(home) = dirs::home_dir() {
-        let bbcli_path = home.join(".bbcli");
-        if bbcli_path.exists() {
-            return Ok(bbcli_path);
+impl ConfigWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        let stamp = file_stamp(&path);
+        Self { path, stamp }
+    }
+
+    /// True once for each time the file is written, created or removed
+    pub fn changed(&mut self) -> bool {
+        let stamp = file_stamp(&self.path);
+        if stamp == self.stamp {
+            return false;
         }
+        self.stamp = stamp;
+        true
     }
+}
+
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Path of the config file (see `paths::config_file`)
+pub fn get_config_path() -> Result<PathBuf> {
+    crate::paths::config_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Try ~/.config/bbcli
-    if let Some(config_dir) = dirs::config_dir() {
-        let bbcli_config = config_dir.join("bbcli");
-        return Ok(bbcli_config);
+    #[test]
+    fn test_default_config_is_clean() {
+        let (config, warnings) = parse_config(DEFAULT_CONFIG).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(
+            toml::to_string(&config).unwrap(),
+            toml::to_string(&Config::default()).unwrap()
+        );
     }
 
-    // Fallback to home directory
-    dirs::home_dir()
-        .map(|h| h.join(".bbcli"))
-        .context("Could not determine config path")
+    #[test]
+    fn test_errors_report_line_and_column() {
+        let err = parse_config("theme = \"dark\"\n[keybindings]\nquit = 'hyper-q'\n").unwrap_err();
+        assert!(err.to_string().contains("line 3, column 8"), "{}", err);
+
+        let err = parse_config("[keybindings.article]\nclose = ['Esc', 'ctrl-']\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+
+        let err = parse_config("[themes.mine]\naccent = \"purplish\"\n").unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_unknown_theme_is_a_warning() {
+        let content = "# Theme\ntheme = \"purple\"\n";
+        let (config, warnings) = parse_config(content).unwrap();
+        assert!(warnings.is_empty());
+
+        let (themes, _) = load_themes(&config.themes, None);
+        let warning = check_theme(content, &config.theme, &themes).unwrap();
+        assert!(warning.starts_with("line 2: unknown theme `purple`"), "{}", warning);
+        assert!(check_theme(content, &ThemeName::new("solarized-dark"), &themes).is_none());
+    }
+
+    #[test]
+    fn test_warns_about_unknown_keys() {
+        let (config, warnings) = parse_config("theme = \"dark\"\n\n[keybindings]\nqiut = 'x'\n\n[colours]\naccent = 50\n").unwrap();
+        assert!(config.theme == ThemeName::new("dark"));
+        assert_eq!(warnings, vec![
+            String::from("line 4: unknown key `keybindings.qiut`"),
+            String::from("line 6: unknown key `colours`"),
+        ]);
+    }
+
+    #[test]
+    fn test_rejects_cache_limits_too_large() {
+        let error = parse_config("[cache]\nmax_size_mb = 99999999999999999\n").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid size"), "{}", error);
+        let error = parse_config("[cache]\nmax_age_days = 999999999999999999\n").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid duration"), "{}", error);
+        assert_eq!(CacheConfig::default().max_bytes().unwrap(), Some(100 * 1024 * 1024));
+    }
+
+    #[test]
+    fn test_watcher_notices_changes() {
+        let path = std::env::temp_dir().join(format!("bbcli-config-watch-{}.toml", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut watcher = ConfigWatcher::new(path.clone());
+        assert!(!watcher.changed());
+
+        std::fs::write(&path, "theme = \"dark\"\n").unwrap();
+        assert!(watcher.changed());
+        assert!(!watcher.changed());
+
+        // Same mtime granularity is possible, so change the size too
+        std::fs::write(&path, "theme = \"light\"\n").unwrap();
+        assert!(watcher.changed());
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(watcher.changed());
+    }
+
+    #[test]
+    fn test_warns_about_duplicate_bindings() {
+        let (_, warnings) = parse_config("[keybindings]\nquit = 'j'\n").unwrap();
+        assert_eq!(warnings, vec![String::from("'j' is bound to more than one action: quit, scroll_down")]);
+    }
 }
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use std::time::Duration;
 
 /// Humanizes a date string from "YYYY-MM-DD HH:MM:SS" format to relative time
 /// Returns strings like "just now", "5 minutes ago", "2 hours ago", "yesterday", etc.
@@ -24,49 +25,68 @@ pub fn humanize_time(date_str: &str) -> String {
 
     if seconds < 0 {
         // Future date
-        return "just now".to_string();
+        "just now".to_string()
     } else if seconds < 60 {
-        return "just now".to_string();
+        "just now".to_string()
     } else if minutes < 60 {
         if minutes == 1 {
-            return "1 minute ago".to_string();
+            "1 minute ago".to_string()
         } else {
-            return format!("{} minutes ago", minutes);
+            format!("{} minutes ago", minutes)
         }
     } else if hours < 24 {
         if hours == 1 {
-            return "1 hour ago".to_string();
+            "1 hour ago".to_string()
         } else {
-            return format!("{} hours ago", hours);
+            format!("{} hours ago", hours)
         }
     } else if days == 1 {
-        return "yesterday".to_string();
+        "yesterday".to_string()
     } else if days < 7 {
-        return format!("{} days ago", days);
+        format!("{} days ago", days)
     } else if days < 30 {
         let weeks = days / 7;
         if weeks == 1 {
-            return "1 week ago".to_string();
+            "1 week ago".to_string()
         } else {
-            return format!("{} weeks ago", weeks);
+            format!("{} weeks ago", weeks)
         }
     } else if days < 365 {
         let months = days / 30;
         if months == 1 {
-            return "1 month ago".to_string();
+            "1 month ago".to_string()
         } else {
-            return format!("{} months ago", months);
+            format!("{} months ago", months)
         }
     } else {
         let years = days / 365;
         if years == 1 {
-            return "1 year ago".to_string();
+            "1 year ago".to_string()
         } else {
-            return format!("{} years ago", years);
+            format!("{} years ago", years)
         }
     }
 }
 
+/// Parses a short duration like "30m", "12h", "7d" or "2w"
+pub fn parse_duration(value: &str) -> anyhow::Result<Duration> {
+    let value = value.trim();
+    let split = value.char_indices().last().map(|(i, _)| i).unwrap_or(0);
+    let (amount, unit) = value.split_at(split);
+
+    let invalid = || anyhow::anyhow!("Invalid duration: '{}'. Use e.g. 30m, 12h, 7d or 2w", value);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+    let seconds = match unit {
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 60 * 60 * 24,
+        "w" => 60 * 60 * 24 * 7,
+        _ => anyhow::bail!("Invalid duration unit: '{}'. Use m, h, d or w", unit),
+    };
+
+    amount.checked_mul(seconds).map(Duration::from_secs).ok_or_else(invalid)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,4 +127,17 @@ mod tests {
         let invalid = "invalid date";
         assert_eq!(humanize_time(invalid), "invalid date");
     }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30m").unwrap(), std::time::Duration::from_secs(1800));
+        assert_eq!(parse_duration("12h").unwrap(), std::time::Duration::from_secs(43200));
+        assert_eq!(parse_duration(" 7d ").unwrap(), std::time::Duration::from_secs(604800));
+        assert_eq!(parse_duration("2w").unwrap(), std::time::Duration::from_secs(1209600));
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("").is_err());
+        // Too large to represent rather than wrapping around
+        assert!(parse_duration("99999999999999999w").unwrap_err().to_string().starts_with("Invalid duration"));
+    }
 }
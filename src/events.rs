@@ -1,9 +1,19 @@
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
-use std::time::Duration;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Size;
+use std::time::{Duration, Instant};
 
-use crate::app::{App, AppMode};
-use crate::config::Config;
-use crate::feeds::get_all_feeds;
+use crate::app::{App, AppMode, Pane};
+use crate::feeds::get_menu_feeds;
+use crate::input::{KeyInput, MouseTarget};
+use crate::keymap::{Action, KeyChord, KeyMode, Lookup};
+use crate::palette::Command;
+use crate::ui::mouse_target;
+
+// Most events read in one go, so a flood of input can't hold off drawing indefinitely
+const MAX_BATCH: usize = 1024;
+
+// Article lines scrolled per wheel step
+const WHEEL_LINES: isize = 3;
 
 pub enum AppAction {
     None,
@@ -12,136 +22,728 @@ pub enum AppAction {
     Resize,
 }
 
-pub fn handle_events(app: &mut App, config: &Config) -> anyhow::Result<AppAction> {
-    if event::poll(Duration::from_millis(100))? {
+pub fn handle_events(app: &mut App) -> anyhow::Result<AppAction> {
+    if !event::poll(Duration::from_millis(100))? {
+        return Ok(AppAction::None);
+    }
+
+    // Read everything already buffered, so a burst of held keys is handled in one go
+    // rather than one redraw per key
+    let mut keys = Vec::new();
+    let mut mouse_events = Vec::new();
+    let mut resized = false;
+    loop {
         match event::read()? {
-            Event::Key(key) if key.kind == KeyEventKind::Press => {
-                return handle_key_event(app, key, config);
+            Event::Key(key) => keys.push(key),
+            Event::Mouse(mouse) => mouse_events.push(mouse),
+            Event::Resize(_, _) => resized = true,
+            _ => {}
+        }
+        if keys.len() + mouse_events.len() >= MAX_BATCH || !event::poll(Duration::ZERO)? {
+            break;
+        }
+    }
+
+    let now = Instant::now();
+    let mut action = handle_keys(app, &keys, now)?;
+    if matches!(action, AppAction::None) && !mouse_events.is_empty() {
+        let (width, height) = crossterm::terminal::size()?;
+        for mouse in mouse_events {
+            action = handle_mouse(app, mouse, Size::new(width, height), now)?;
+            if !matches!(action, AppAction::None) {
+                break;
+            }
+        }
+    }
+
+    match action {
+        AppAction::None if resized => Ok(AppAction::Resize),
+        action => Ok(action),
+    }
+}
+
+/// Clicks select, double-clicks open, and the wheel scrolls whatever is under the pointer
+fn handle_mouse(app: &mut App, mouse: MouseEvent, size: Size, now: Instant) -> anyhow::Result<AppAction> {
+    // Prompts keep the keyboard's full attention
+    if app.confirm_open || matches!(app.mode, AppMode::Search | AppMode::ArchiveSearch | AppMode::ArticleSearch | AppMode::Palette) {
+        return Ok(AppAction::None);
+    }
+    let Some(target) = mouse_target(app, size, mouse.column, mouse.row) else {
+        return Ok(AppAction::None);
+    };
+
+    match (mouse.kind, target) {
+        (MouseEventKind::Down(MouseButton::Left), MouseTarget::Story(index)) => {
+            let double = app.input.click(target, now);
+            app.focus_pane(Pane::Stories);
+            app.go_to_story(index + 1);
+            if double {
+                app.fetch_and_show_article();
             }
-            Event::Resize(_, _) => {
-                return Ok(AppAction::Resize);
+        }
+        (MouseEventKind::Down(MouseButton::Left), MouseTarget::Feed(index)) => {
+            app.focus_pane(Pane::Sidebar);
+            app.feed_menu_selected = index;
+            if app.input.click(target, now) {
+                return Ok(select_menu_feed(app));
             }
-            _ => {}
+        }
+        (MouseEventKind::Down(MouseButton::Left), MouseTarget::Tab(index)) => {
+            return Ok(tab_changed(app.switch_tab(index)));
+        }
+        (MouseEventKind::Down(MouseButton::Left), MouseTarget::Ticker) if app.jump_to_ticker_article() => {
+            return Ok(AppAction::FeedChanged);
+        }
+        (MouseEventKind::Down(MouseButton::Left), MouseTarget::Preview) => app.focus_right(),
+        (MouseEventKind::Down(MouseButton::Left), MouseTarget::Article) => app.focus_pane(Pane::Reader),
+        (MouseEventKind::ScrollDown, MouseTarget::Feed(_) | MouseTarget::FeedMenu) => {
+            app.feed_menu_next(get_menu_feeds().len());
+        }
+        (MouseEventKind::ScrollUp, MouseTarget::Feed(_) | MouseTarget::FeedMenu) => app.feed_menu_previous(),
+        (MouseEventKind::ScrollDown, MouseTarget::Article) => app.scroll_article_by(WHEEL_LINES),
+        (MouseEventKind::ScrollUp, MouseTarget::Article) => app.scroll_article_by(-WHEEL_LINES),
+        (MouseEventKind::ScrollDown, MouseTarget::Story(_) | MouseTarget::Preview) => app.move_selection(1),
+        (MouseEventKind::ScrollUp, MouseTarget::Story(_) | MouseTarget::Preview) => app.move_selection(-1),
+        _ => {}
+    }
+    Ok(AppAction::None)
+}
+
+// Run an item picked from the command palette
+fn run_command(app: &mut App, command: Command) -> anyhow::Result<AppAction> {
+    match command {
+        Command::Action(mode, action) => return run_action(app, mode, action, None, false),
+        Command::Sort(order) => app.set_sort_order(order),
+        Command::Theme(name) => app.set_theme(&name),
+        Command::ImageProtocol(protocol) => app.image_protocol = protocol,
+        Command::Feed(feed) => {
+            app.select_feed(feed);
+            return Ok(AppAction::FeedChanged);
+        }
+        Command::Story(index) => app.go_to_story(index + 1),
+    }
+    Ok(AppAction::None)
+}
+
+// A tab that hasn't been loaded yet is fetched when it's switched to
+fn tab_changed(needs_fetch: bool) -> AppAction {
+    if needs_fetch { AppAction::FeedChanged } else { AppAction::None }
+}
+
+// Switch to the feed highlighted in the feed menu
+fn select_menu_feed(app: &mut App) -> AppAction {
+    match get_menu_feeds().get(app.feed_menu_selected) {
+        Some(feed) => {
+            app.select_feed(feed.clone());
+            AppAction::FeedChanged
+        }
+        None => AppAction::None,
+    }
+}
+
+/// Handle key events that were read together
+fn handle_keys(app: &mut App, keys: &[KeyEvent], now: Instant) -> anyhow::Result<AppAction> {
+    for input in app.input.process(keys, now) {
+        let action = handle_input(app, &input)?;
+        // Keys after a refresh or feed change are dropped, the stories they'd act on are being replaced
+        if !matches!(action, AppAction::None) {
+            return Ok(action);
+        }
+    }
+    Ok(AppAction::None)
+}
+
+fn handle_input(app: &mut App, input: &KeyInput) -> anyhow::Result<AppAction> {
+    app.dismiss_config_warning();
+    let chord = KeyChord::from(input.key);
+
+    // Opening was asked for in a burst of input: y opens, any other key cancels
+    if app.confirm_open {
+        if input.auto_repeat {
+            return Ok(AppAction::None);
+        }
+        app.confirm_open = false;
+        if chord == KeyChord::new(KeyCode::Char('y'), KeyModifiers::NONE) {
+            app.open_selected()?;
+        }
+        return Ok(AppAction::None);
+    }
+
+    let mode = key_mode(app);
+    let single = match (app.mode == AppMode::Normal, app.pending_keys.is_empty()) {
+        (true, true) => app.keymap.lookup(mode, &[chord]),
+        _ => Lookup::None,
+    };
+
+    // Holding an open key never opens more than once
+    if input.auto_repeat && matches!(single, Lookup::Action(Action::Open | Action::OpenNewTab)) {
+        return Ok(AppAction::None);
+    }
+
+    // A held movement key makes one move by the number of presses
+    if input.repeats > 0 && matches!(single, Lookup::Action(action) if is_move(action)) {
+        let count = app.pending_count.unwrap_or(1);
+        app.pending_count = Some(count.saturating_mul(input.presses()));
+        return handle_key_event(app, input.key, false);
+    }
+
+    let confirm_open = app.input.confirm_open(input);
+    for _ in 0..input.presses() {
+        let action = handle_key_event(app, input.key, confirm_open)?;
+        if !matches!(action, AppAction::None) {
+            return Ok(action);
         }
     }
     Ok(AppAction::None)
 }
 
-fn handle_key_event(app: &mut App, key: KeyEvent, config: &Config) -> anyhow::Result<AppAction> {
-    let kb = &config.keybindings;
+fn handle_key_event(app: &mut App, key: KeyEvent, confirm_open: bool) -> anyhow::Result<AppAction> {
+    // Handle search input mode separately
+    if app.mode == AppMode::Search {
+        match key.code {
+            KeyCode::Enter => app.confirm_search(),
+            KeyCode::Esc => app.clear_search(),
+            KeyCode::Backspace => app.search_pop(),
+            KeyCode::Char(c) => app.search_push(c),
+            _ => {}
+        }
+        return Ok(AppAction::None);
+    }
 
-    // Handle help menu mode separately
-    if app.mode == AppMode::Help {
+    // Search within the open article
+    if app.mode == AppMode::ArticleSearch {
         match key.code {
-            KeyCode::Char('?') | KeyCode::Esc => app.toggle_help_menu(),
+            KeyCode::Enter => app.confirm_article_search(),
+            KeyCode::Esc => app.clear_article_search(),
+            KeyCode::Backspace => app.article_search_pop(),
+            KeyCode::Char(c) => app.article_search_push(c),
             _ => {}
         }
         return Ok(AppAction::None);
     }
 
-    // Handle feed menu mode separately
-    if app.mode == AppMode::FeedMenu {
+    // Command palette: type to filter, Enter runs the selected item
+    if app.mode == AppMode::Palette {
+        let Some(palette) = app.palette.as_mut() else {
+            app.close_palette();
+            return Ok(AppAction::None);
+        };
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                let feeds = get_all_feeds();
-                app.feed_menu_next(feeds.len());
-            }
-            KeyCode::Char('k') | KeyCode::Up => app.feed_menu_previous(),
             KeyCode::Enter => {
-                let feeds = get_all_feeds();
-                if let Some(feed) = feeds.get(app.feed_menu_selected) {
-                    app.select_feed(feed.clone());
-                    return Ok(AppAction::FeedChanged);
+                let command = palette.selected_item().map(|item| item.command.clone());
+                app.close_palette();
+                if let Some(command) = command {
+                    return run_command(app, command);
                 }
             }
-            KeyCode::Char('f') | KeyCode::Esc => app.toggle_feed_menu(),
+            KeyCode::Esc => app.close_palette(),
+            KeyCode::Backspace => palette.pop(),
+            KeyCode::Down | KeyCode::Tab => palette.next(),
+            KeyCode::Up | KeyCode::BackTab => palette.previous(),
+            KeyCode::Char('n') if ctrl => palette.next(),
+            KeyCode::Char('p') if ctrl => palette.previous(),
+            KeyCode::Char(c) if !ctrl => palette.push(c),
             _ => {}
         }
         return Ok(AppAction::None);
     }
 
-    // ARTICLE VIEW MODE: Handle scrolling within article
-    if app.show_full_article {
+    // Handle archive search input mode separately
+    if app.mode == AppMode::ArchiveSearch {
         match key.code {
-            KeyCode::Up | KeyCode::Char('k') => app.scroll_article_up(),
-            KeyCode::Down | KeyCode::Char('j') => app.scroll_article_down(),
-            KeyCode::Char(c) if c == kb.open => app.open_selected()?,
-            KeyCode::Enter | KeyCode::Tab | KeyCode::Esc => app.toggle_article_view(),
+            KeyCode::Enter if app.submit_archive_search() => return Ok(AppAction::FeedChanged),
+            KeyCode::Enter | KeyCode::Esc => app.cancel_archive_search(),
+            KeyCode::Backspace => app.archive_search_pop(),
+            KeyCode::Char(c) => app.archive_search_push(c),
             _ => {}
         }
         return Ok(AppAction::None);
     }
 
-    // PREVIEW PANE PROTECTION: Block scrolling keys when preview is open
-    // This prevents Ghostty event buffering catastrophe (scroll storm detection is backup)
-    if app.show_preview && !app.show_full_article {
-        let is_scroll_key = matches!(
-            key.code,
-            KeyCode::Down | KeyCode::Up
-        ) || matches!(
-            key.code,
-            KeyCode::Char(c) if c == kb.scroll_down || c == kb.scroll_up || c == kb.scroll_bottom || c == kb.latest
-        );
-
-        if is_scroll_key {
-            // Drain all buffered scroll events to prevent delay when Tab is pressed
-            // This prevents 5+ second delays when keys were held down
-            while event::poll(Duration::ZERO)? {
-                if let Event::Key(next_key) = event::read()? {
-                    if next_key.kind == KeyEventKind::Press {
-                        // Check if this is also a scroll key
-                        let is_next_scroll = matches!(
-                            next_key.code,
-                            KeyCode::Down | KeyCode::Up
-                        ) || matches!(
-                            next_key.code,
-                            KeyCode::Char(c) if c == kb.scroll_down || c == kb.scroll_up || c == kb.scroll_bottom || c == kb.latest
-                        );
-
-                        if !is_next_scroll {
-                            // Found a non-scroll key (like Tab!), process it immediately
-                            return handle_key_event(app, next_key, config);
-                        }
-                        // Otherwise it's another scroll key, discard and continue draining
-                    }
-                }
-            }
-            // All buffered scroll events drained, return
+    let mode = key_mode(app);
+    let chord = KeyChord::from(key);
+
+    // Count prefix: digits that aren't bound to anything, like the 12 in "12G"
+    if let Some(digit) = count_digit(app, mode, chord) {
+        app.push_count_digit(digit);
+        return Ok(AppAction::None);
+    }
+
+    // A count followed by Enter selects that story number
+    if mode == KeyMode::Normal && chord == KeyChord::new(KeyCode::Enter, KeyModifiers::NONE) && app.pending_keys.is_empty() {
+        if let Some(number) = app.pending_count.take() {
+            app.go_to_story(number);
             return Ok(AppAction::None);
         }
     }
 
-    // Normal mode key handling
-    match key.code {
-        KeyCode::Char(c) if c == kb.quit => app.quit(),
-        KeyCode::Char(c) if c == kb.scroll_down => app.next(),
-        KeyCode::Char(c) if c == kb.scroll_up => app.previous(),
-        KeyCode::Char(c) if c == kb.scroll_bottom => app.scroll_to_bottom(),
-        KeyCode::Char(c) if c == kb.open => app.open_selected()?,
-        KeyCode::Char(c) if c == kb.open_new_tab => app.open_selected_new_tab()?,
-        KeyCode::Char(c) if c == kb.latest => app.scroll_to_top(),
-        KeyCode::Char(c) if c == kb.refresh => return Ok(AppAction::Refresh),
-        KeyCode::Char('f') => app.toggle_feed_menu(),
-        KeyCode::Char('s') => app.cycle_sort_order(),
-        KeyCode::Char('t') => app.toggle_date_format(),
-        KeyCode::Char('T') => app.cycle_theme(),
-        KeyCode::Char('p') => app.cycle_image_protocol(),
-        KeyCode::Char('?') => app.toggle_help_menu(),
-        KeyCode::Char('a') => app.fetch_and_show_article(),
-        KeyCode::Char(' ') => {
-            // Jump to current ticker article
-            if app.jump_to_ticker_article() {
-                return Ok(AppAction::FeedChanged);
+    app.pending_keys.push(chord);
+    // The feed sidebar has the feed menu's keys, and the story list's for everything else
+    let (mode, lookup) = match app.keymap.lookup(mode, &app.pending_keys) {
+        Lookup::None if app.is_sidebar_focused() => {
+            (KeyMode::Normal, app.keymap.lookup(KeyMode::Normal, &app.pending_keys))
+        }
+        lookup => (mode, lookup),
+    };
+    match lookup {
+        Lookup::Action(action) => {
+            app.pending_keys.clear();
+            let count = app.pending_count.take();
+            run_action(app, mode, action, count, confirm_open)
+        }
+        // Part of a longer sequence like "gg", wait for the next key
+        Lookup::Pending => Ok(AppAction::None),
+        Lookup::None => {
+            // A key that breaks a sequence is tried again on its own
+            let retry = app.pending_keys.len() > 1;
+            app.pending_keys.clear();
+            if !retry {
+                app.pending_count = None;
+            }
+            if retry {
+                return handle_key_event(app, key, confirm_open);
             }
+            Ok(AppAction::None)
         }
-        KeyCode::Tab => app.toggle_preview(),
-        KeyCode::Enter => app.fetch_and_show_article(),
-        // Also support arrow keys
-        KeyCode::Down => app.next(),
-        KeyCode::Up => app.previous(),
-        KeyCode::Esc => app.quit(),
-        _ => {}
+    }
+}
+
+/// Which bindings apply in the app's current state
+pub fn key_mode(app: &App) -> KeyMode {
+    match app.mode {
+        AppMode::Help => KeyMode::Help,
+        AppMode::FeedMenu => KeyMode::FeedMenu,
+        _ if app.is_sidebar_focused() => KeyMode::FeedMenu,
+        _ if app.is_reading_article() => KeyMode::Article,
+        _ => KeyMode::Normal,
+    }
+}
+
+// Digits only start a count where they aren't bound, and 0 can't start one
+fn count_digit(app: &App, mode: KeyMode, chord: KeyChord) -> Option<usize> {
+    let KeyCode::Char(c) = chord.code else {
+        return None;
+    };
+    let digit = c.to_digit(10)? as usize;
+    let counting = app.pending_count.is_some() || digit != 0;
+    let usable = matches!(mode, KeyMode::Normal | KeyMode::Article)
+        && chord.modifiers.is_empty()
+        && app.pending_keys.is_empty()
+        && app.keymap.lookup(mode, &[chord]) == Lookup::None;
+    (counting && usable).then_some(digit)
+}
+
+// Actions that take a count as a distance
+fn is_move(action: Action) -> bool {
+    matches!(
+        action,
+        Action::ScrollDown | Action::ScrollUp | Action::PageDown | Action::PageUp | Action::HalfPageDown | Action::HalfPageUp
+    )
+}
+
+// Moves repeat by the count; other actions ignore it
+fn run_action(
+    app: &mut App,
+    mode: KeyMode,
+    action: Action,
+    count: Option<usize>,
+    confirm_open: bool,
+) -> anyhow::Result<AppAction> {
+    let repeat = count.unwrap_or(1) as isize;
+    let list_page = app.list_page_size() as isize;
+    let article_page = app.article_page_size() as isize;
+
+    match (mode, action) {
+        (KeyMode::Help, Action::Close) => app.toggle_help_menu(),
+
+        (KeyMode::FeedMenu, Action::ScrollDown) => app.feed_menu_next(get_menu_feeds().len()),
+        (KeyMode::FeedMenu, Action::ScrollUp) => app.feed_menu_previous(),
+        (KeyMode::FeedMenu, Action::Select) => return Ok(select_menu_feed(app)),
+        (KeyMode::FeedMenu, Action::OpenTab) => {
+            let feed = get_menu_feeds().get(app.feed_menu_selected).cloned();
+            if let Some(feed) = feed {
+                return Ok(tab_changed(app.open_tab(feed)));
+            }
+        }
+        (KeyMode::FeedMenu, Action::Close) if app.mode == AppMode::FeedMenu => app.toggle_feed_menu(),
+        (KeyMode::FeedMenu, Action::Close) => app.focus_pane(Pane::Stories),
+
+        // ARTICLE VIEW MODE: Handle scrolling within article
+        (KeyMode::Article, Action::ScrollDown) => app.scroll_article_by(repeat),
+        (KeyMode::Article, Action::ScrollUp) => app.scroll_article_by(-repeat),
+        (KeyMode::Article, Action::PageDown) => app.scroll_article_by(article_page.saturating_mul(repeat)),
+        (KeyMode::Article, Action::PageUp) => app.scroll_article_by(-article_page.saturating_mul(repeat)),
+        (KeyMode::Article, Action::HalfPageDown) => app.scroll_article_by((article_page / 2).saturating_mul(repeat)),
+        (KeyMode::Article, Action::HalfPageUp) => app.scroll_article_by(-(article_page / 2).saturating_mul(repeat)),
+        (KeyMode::Article, Action::Latest) => app.scroll_article_to(0),
+        (KeyMode::Article, Action::ScrollBottom) => app.scroll_article_to(usize::MAX),
+        (KeyMode::Article, Action::Search) => app.start_article_search(),
+        (KeyMode::Article, Action::NextMatch) => app.next_article_match(repeat),
+        (KeyMode::Article, Action::PreviousMatch) => app.next_article_match(-repeat),
+        // Esc clears the highlighted matches before closing the article
+        (KeyMode::Article, Action::Close) if !app.article_query.is_empty() => app.clear_article_search(),
+        (KeyMode::Article, Action::Close) => app.toggle_article_view(),
+
+        // Normal mode
+        (_, Action::Quit) => app.quit(),
+        // Esc clears an active search filter before quitting
+        (_, Action::Back) if app.is_search_active() => app.clear_search(),
+        (_, Action::Back) => app.quit(),
+        (_, Action::ScrollDown) => app.move_selection(repeat),
+        (_, Action::ScrollUp) => app.move_selection(-repeat),
+        // With a count, G and l go to that story number, like vim's 12G
+        (_, Action::ScrollBottom | Action::Latest) if count.is_some() => app.go_to_story(repeat as usize),
+        (_, Action::ScrollBottom) => app.scroll_to_bottom(),
+        (_, Action::Latest) => app.scroll_to_top(),
+        (_, Action::PageDown) => app.move_selection(list_page.saturating_mul(repeat)),
+        (_, Action::PageUp) => app.move_selection(-list_page.saturating_mul(repeat)),
+        (_, Action::HalfPageDown) => app.move_selection((list_page / 2).max(1).saturating_mul(repeat)),
+        (_, Action::HalfPageUp) => app.move_selection(-(list_page / 2).max(1).saturating_mul(repeat)),
+        (_, Action::Open | Action::OpenNewTab) if confirm_open => app.request_open_confirmation(),
+        (_, Action::Open) => app.open_selected()?,
+        (_, Action::OpenNewTab) => app.open_selected_new_tab()?,
+        (_, Action::Refresh) => return Ok(AppAction::Refresh),
+        // Jump to current ticker article
+        (_, Action::Ticker) if app.jump_to_ticker_article() => return Ok(AppAction::FeedChanged),
+        (_, Action::Ticker) => {}
+        (_, Action::ToggleRead) => app.toggle_read(),
+        (_, Action::MarkAllRead) => app.mark_all_read(),
+        (_, Action::Bookmark) => app.toggle_bookmark(),
+        (_, Action::Search) => app.start_search(),
+        (_, Action::NextMatch) => app.next_match(),
+        (_, Action::PreviousMatch) => app.previous_match(),
+        (_, Action::ArchiveSearch) => app.start_archive_search(),
+        (_, Action::Preview) => app.toggle_preview(),
+        (_, Action::Article) => app.fetch_and_show_article(),
+        (_, Action::FeedMenu) => app.toggle_feed_menu(),
+        (_, Action::Sort) => app.cycle_sort_order(),
+        (_, Action::DateFormat) => app.toggle_date_format(),
+        (_, Action::Theme) => app.cycle_theme(),
+        (_, Action::ImageProtocol) => app.cycle_image_protocol(),
+        (_, Action::Help) => app.toggle_help_menu(),
+        (_, Action::Palette) => app.open_palette(mode),
+        (_, Action::Layout) => app.toggle_layout(),
+        (_, Action::FocusLeft) => app.focus_left(),
+        (_, Action::FocusRight) => app.focus_right(),
+        (_, Action::GrowPreview) => app.resize_preview(repeat),
+        (_, Action::ShrinkPreview) => app.resize_preview(-repeat),
+        (_, Action::MovePreview) => app.toggle_preview_position(),
+        // With a count, gt goes to that tab number, like vim's 3gt
+        (_, Action::NextTab) if count.is_some() => return Ok(tab_changed(app.switch_tab(repeat as usize - 1))),
+        (_, Action::NextTab) => return Ok(tab_changed(app.cycle_tab(1))),
+        (_, Action::PreviousTab) => return Ok(tab_changed(app.cycle_tab(-repeat))),
+        (_, Action::GoToTab(number)) => return Ok(tab_changed(app.switch_tab(number as usize - 1))),
+        (_, Action::CloseTab) => return Ok(tab_changed(app.close_tab())),
+        // Only bound in the menus
+        (_, Action::Select | Action::Close | Action::OpenTab) => {}
     }
 
     Ok(AppAction::None)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::{NewsStory, SortOrder};
+    use crate::theme::Theme;
+
+    fn stories(count: usize, section: &str) -> Vec<NewsStory> {
+        (1..=count).map(|i| NewsStory {
+            title: format!("Story {}", i),
+            description: String::new(),
+            link: format!("https://www.bbc.co.uk/{}/{}", section, i),
+            pub_date: String::from("2025-01-01 12:00:00"),
+            category: String::from("News"),
+            image_url: None,
+        }).collect()
+    }
+
+    fn app_with_stories(count: usize) -> App {
+        let mut app = App::new(Theme::light());
+        app.update_stories(stories(count, "news"));
+        app.list_height = 20;
+        app.article_view = ratatui::layout::Size::new(78, 18);
+        app
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for token in keys.split_whitespace() {
+            let chord = crate::keymap::KeySequence::parse(token).unwrap();
+            for key in chord.0 {
+                handle_key_event(app, KeyEvent::new(key.code, key.modifiers), false).unwrap();
+            }
+        }
+    }
+
+    fn char_keys(keys: &str) -> Vec<KeyEvent> {
+        keys.chars().map(|c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)).collect()
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_buffered_burst_moves_once() {
+        let mut app = app_with_stories(30);
+        let start = Instant::now();
+        handle_keys(&mut app, &char_keys(&"j".repeat(12)), start).unwrap();
+        assert_eq!(app.selected, 12);
+
+        // Separate presses still move one at a time
+        handle_keys(&mut app, &char_keys("k"), start + ms(200)).unwrap();
+        handle_keys(&mut app, &char_keys("k"), start + ms(400)).unwrap();
+        assert_eq!(app.selected, 10);
+    }
+
+    #[test]
+    fn test_open_in_burst_asks_first() {
+        let mut app = app_with_stories(30);
+        let start = Instant::now();
+        handle_keys(&mut app, &char_keys("jjjjjo"), start).unwrap();
+        assert!(app.confirm_open);
+        assert_eq!(app.selected, 5);
+
+        // Held open key repeats don't answer the prompt, anything but y cancels it
+        handle_keys(&mut app, &char_keys("oo"), start + ms(10)).unwrap();
+        assert!(app.confirm_open);
+        handle_keys(&mut app, &char_keys("n"), start + ms(100)).unwrap();
+        assert!(!app.confirm_open);
+        assert!(!app.is_read(5));
+    }
+
+    #[test]
+    fn test_settled_input_has_no_prompt() {
+        let mut app = app_with_stories(30);
+        app.input = crate::input::InputFilter::new(&crate::config::InputConfig { settle_ms: 0, ..Default::default() });
+        let start = Instant::now();
+        handle_keys(&mut app, &char_keys("jjj"), start).unwrap();
+        handle_keys(&mut app, &char_keys("m"), start + ms(100)).unwrap();
+        assert!(!app.confirm_open);
+        assert!(app.is_read(3));
+    }
+
+    #[test]
+    fn test_count_repeats_moves() {
+        let mut app = app_with_stories(30);
+        press(&mut app, "5j");
+        assert_eq!(app.selected, 5);
+        press(&mut app, "3k");
+        assert_eq!(app.selected, 2);
+        press(&mut app, "99j");
+        assert_eq!(app.selected, 29);
+        assert_eq!(app.pending_count, None);
+
+        // A very long count is capped instead of overflowing into a move the other way
+        press(&mut app, "gg");
+        for key in char_keys(&"9".repeat(25)) {
+            handle_key_event(&mut app, key, false).unwrap();
+        }
+        assert_eq!(app.pending_count, Some(crate::app::MAX_COUNT));
+        press(&mut app, "j");
+        assert_eq!(app.selected, 29);
+        for key in char_keys(&"9".repeat(25)) {
+            handle_key_event(&mut app, key, false).unwrap();
+        }
+        press(&mut app, "PageUp");
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn test_count_jumps_to_story_number() {
+        let mut app = app_with_stories(30);
+        press(&mut app, "12G");
+        assert_eq!(app.selected, 11);
+        press(&mut app, "17 Enter");
+        assert_eq!(app.selected, 16);
+        assert!(!app.show_full_article);
+
+        // Without a count, Enter still opens the article and G still goes to the bottom
+        press(&mut app, "G");
+        assert_eq!(app.selected, 29);
+    }
+
+    #[test]
+    fn test_page_moves_use_screen_height() {
+        let mut app = app_with_stories(30);
+        press(&mut app, "PageDown");
+        assert_eq!(app.selected, 10);
+        press(&mut app, "ctrl-u");
+        assert_eq!(app.selected, 5);
+        press(&mut app, "2 ctrl-f");
+        assert_eq!(app.selected, 25);
+    }
+
+    #[test]
+    fn test_zero_and_other_keys_reset_count() {
+        let mut app = app_with_stories(30);
+        press(&mut app, "0");
+        assert_eq!(app.pending_count, None);
+        press(&mut app, "10");
+        assert_eq!(app.pending_count, Some(10));
+        press(&mut app, "x j");
+        assert_eq!(app.selected, 1);
+    }
+
+    fn click(app: &mut App, column: u16, row: u16, now: Instant) -> AppAction {
+        let mouse = MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        };
+        handle_mouse(app, mouse, Size::new(100, 40), now).unwrap()
+    }
+
+    fn wheel(app: &mut App, kind: MouseEventKind, row: u16) {
+        let mouse = MouseEvent { kind, column: 10, row, modifiers: KeyModifiers::NONE };
+        handle_mouse(app, mouse, Size::new(100, 40), Instant::now()).unwrap();
+    }
+
+    #[test]
+    fn test_click_selects_story() {
+        let mut app = app_with_stories(30);
+        let start = Instant::now();
+        // Header is three rows, then two rows per story. Clicks are spaced out so none is a double-click.
+        click(&mut app, 10, 7, start);
+        assert_eq!(app.selected, 2);
+        click(&mut app, 10, 8, start + ms(1000));
+        assert_eq!(app.selected, 2);
+
+        // Once scrolled, rows map to stories further down: 18 fit, so story 25 is at the bottom
+        app.go_to_story(25);
+        click(&mut app, 10, 3, start + ms(2000));
+        assert_eq!(app.selected, 25 - 18);
+        assert!(!app.show_full_article);
+    }
+
+    #[test]
+    fn test_wheel_scrolls_list_and_article() {
+        let mut app = app_with_stories(30);
+        wheel(&mut app, MouseEventKind::ScrollDown, 10);
+        wheel(&mut app, MouseEventKind::ScrollDown, 10);
+        assert_eq!(app.selected, 2);
+
+        let text = vec!["Line"; 50].join("\n");
+        app.article_cache.insert(app.stories[2].link.clone(), text);
+        app.show_full_article = true;
+        wheel(&mut app, MouseEventKind::ScrollDown, 10);
+        assert_eq!(app.article_scroll_offset, 3);
+        assert_eq!(app.selected, 2);
+    }
+
+    #[test]
+    fn test_article_keys_jump_and_search() {
+        let mut app = app_with_stories(3);
+        let text: Vec<String> = (1..=40).map(|i| format!("Line {}", i)).collect();
+        app.article_cache.insert(app.stories[0].link.clone(), text.join("\n"));
+        press(&mut app, "Enter");
+        assert!(app.show_full_article);
+
+        press(&mut app, "End");
+        assert_eq!(app.article_scroll_offset, 22);
+        press(&mut app, "gg");
+        assert_eq!(app.article_scroll_offset, 0);
+
+        press(&mut app, "/");
+        handle_keys(&mut app, &char_keys("line 3"), Instant::now()).unwrap();
+        press(&mut app, "Enter n");
+        assert_eq!(app.article_match, Some(1));
+        assert_eq!(app.article_scroll_offset, 22);
+
+        // Esc clears the search first, then closes the article
+        press(&mut app, "Esc");
+        assert!(app.article_query.is_empty() && app.show_full_article);
+        press(&mut app, "Esc");
+        assert!(!app.show_full_article);
+    }
+
+    #[test]
+    fn test_double_click_picks_feed() {
+        let mut app = app_with_stories(30);
+        app.toggle_feed_menu();
+        let size = Size::new(100, 40);
+        let feed_count = get_menu_feeds().len() as u16;
+        let first_row = (size.height - (feed_count + 4)) / 2 + 1;
+
+        let start = Instant::now();
+        assert!(matches!(click(&mut app, 50, first_row + 2, start), AppAction::None));
+        assert_eq!(app.feed_menu_selected, 2);
+        assert!(matches!(click(&mut app, 50, first_row + 2, start + ms(100)), AppAction::FeedChanged));
+        assert_eq!(app.current_feed.url, get_menu_feeds()[2].url);
+    }
+
+    #[test]
+    fn test_focus_moves_between_panes() {
+        let mut app = app_with_stories(30);
+        press(&mut app, "Left");
+        assert_eq!(app.focus, Pane::Stories);  // No sidebar in the compact layout
+
+        press(&mut app, "L Left");
+        assert!(app.is_sidebar_focused());
+        assert_eq!(get_menu_feeds()[app.feed_menu_selected].url, app.current_feed.url);
+        press(&mut app, "j Right");
+        assert_eq!(app.focus, Pane::Stories);
+
+        press(&mut app, "ctrl-h Esc");
+        assert_eq!(app.focus, Pane::Stories);
+        assert_eq!(app.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_sidebar_falls_back_to_list_keys() {
+        let mut app = app_with_stories(30);
+        press(&mut app, "L Left j j");
+        assert_eq!(app.feed_menu_selected, 2);
+        assert_eq!(app.selected, 0);
+
+        // Not a feed menu key, so it sorts the story list
+        press(&mut app, "s");
+        assert_eq!(app.sort_order, SortOrder::DateNewest);
+        assert!(app.is_sidebar_focused());
+    }
+
+    #[test]
+    fn test_sidebar_click_focuses_it() {
+        let mut app = app_with_stories(30);
+        app.toggle_layout();
+        // Inside the sidebar border, third feed
+        click(&mut app, 5, 6, Instant::now());
+        assert!(app.is_sidebar_focused());
+        assert_eq!(app.feed_menu_selected, 2);
+
+        click(&mut app, 40, 7, Instant::now() + ms(1000));
+        assert_eq!(app.focus, Pane::Stories);
+        assert_eq!(app.selected, 2);
+    }
+
+    #[test]
+    fn test_tabs_keep_their_own_position() {
+        let mut app = app_with_stories(30);
+        press(&mut app, "5j f");
+        app.feed_menu_selected = get_menu_feeds().iter().position(|feed| feed.name == "World").unwrap();
+        let key = KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE);
+        assert!(matches!(handle_key_event(&mut app, key, false).unwrap(), AppAction::FeedChanged));
+        assert_eq!((app.active_tab, app.current_feed.name.as_str()), (1, "World"));
+        assert_eq!(app.mode, AppMode::Normal);
+        app.update_stories(stories(10, "world"));
+        press(&mut app, "3j");
+
+        press(&mut app, "gt");
+        assert_eq!((app.active_tab, app.selected), (0, 5));
+        assert_eq!(app.stories[0].link, "https://www.bbc.co.uk/news/1");
+        press(&mut app, "gT");
+        assert_eq!((app.active_tab, app.selected), (1, 3));
+        press(&mut app, "alt-1");
+        assert_eq!(app.active_tab, 0);
+        press(&mut app, "2gt");
+        assert_eq!(app.active_tab, 1);
+
+        press(&mut app, "X");
+        assert_eq!(app.tab_names(), vec!["Top Stories"]);
+        assert_eq!(app.selected, 5);
+        press(&mut app, "X");
+        assert_eq!(app.tabs.len(), 1);
+    }
+}
@@ -11,6 +11,14 @@ impl Feed {
             url: url.to_string(),
         }
     }
+
+    pub fn is_saved(&self) -> bool {
+        self.url == SAVED_FEED_URL
+    }
+
+    pub fn is_archive(&self) -> bool {
+        self.url == ARCHIVE_FEED_URL
+    }
 }
 
 pub fn get_all_feeds() -> Vec<Feed> {
@@ -38,6 +46,23 @@ pub fn get_all_feeds() -> Vec<Feed> {
     ]
 }
 
+/// Pseudo-feed URL for bookmarked stories (never fetched from the network)
+pub const SAVED_FEED_URL: &str = "bbcli://saved";
+
+/// Pseudo-feed URL for archive search results
+pub const ARCHIVE_FEED_URL: &str = "bbcli://archive";
+
+pub fn get_saved_feed() -> Feed {
+    Feed::new("Saved", SAVED_FEED_URL)
+}
+
+/// Feeds shown in the feed menu: all BBC feeds plus the Saved pseudo-feed
+pub fn get_menu_feeds() -> Vec<Feed> {
+    let mut feeds = get_all_feeds();
+    feeds.push(get_saved_feed());
+    feeds
+}
+
 pub fn get_default_feed() -> Feed {
     Feed::new("Top Stories", "https://feeds.bbci.co.uk/news/rss.xml")
 }
@@ -3,12 +3,20 @@ use std::collections::HashMap;
 use std::io::Cursor;
 use std::sync::{Arc, Mutex};
 
+use crate::cache::Cache;
+
 /// Simple in-memory image cache
 pub struct ImageCache {
     cache: HashMap<String, DynamicImage>,
     max_size: usize,
 }
 
+impl Default for ImageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ImageCache {
     pub fn new() -> Self {
         Self {
@@ -42,16 +50,12 @@ impl ImageCache {
         }
     }
 
-    /// Download image from URL
+    /// Load image from the disk cache, or download it (and cache it on disk)
     fn download_image(url: &str) -> anyhow::Result<DynamicImage> {
-        // Use BBC-compatible user agent and timeout
-        let client = reqwest::blocking::Client::builder()
-            .user_agent("Mozilla/5.0 (Linux; Android 10; SM-A307G) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/86.0.4240.198 Safari/537.36")
-            .timeout(std::time::Duration::from_secs(15))
-            .build()?;
-
-        let response = client.get(url).send()?;
-        let bytes = response.bytes()?;
+        let bytes = match Cache::new().ok().and_then(|cache| cache.load_image(url)) {
+            Some(bytes) => bytes,
+            None => download_image_bytes(url)?,
+        };
         let img = ImageReader::new(Cursor::new(bytes))
             .with_guessed_format()?
             .decode()?;
@@ -105,6 +109,24 @@ impl ImageCache {
     }
 }
 
+/// Download image bytes and save them to the disk cache for offline use
+pub fn download_image_bytes(url: &str) -> anyhow::Result<Vec<u8>> {
+    // Use BBC-compatible user agent and timeout
+    let client = reqwest::blocking::Client::builder()
+        .user_agent("Mozilla/5.0 (Linux; Android 10; SM-A307G) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/86.0.4240.198 Safari/537.36")
+        .timeout(std::time::Duration::from_secs(15))
+        .build()?;
+
+    let response = client.get(url).send()?.error_for_status()?;
+    let bytes = response.bytes()?.to_vec();
+
+    if let Ok(cache) = Cache::new() {
+        let _ = cache.save_image(url, &bytes);
+    }
+
+    Ok(bytes)
+}
+
 // Helper to draw a rectangle
 fn draw_rect(img: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
     for dy in 0..height {
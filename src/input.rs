@@ -0,0 +1,205 @@
+use crossterm::event::{KeyEvent, KeyEventKind};
+use std::time::{Duration, Instant};
+
+use crate::config::InputConfig;
+use crate::keymap::KeyChord;
+
+/// A key press after filtering, standing for one or more presses of the same key
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyInput {
+    pub key: KeyEvent,
+    pub repeats: usize,     // Extra presses folded into this one
+    pub auto_repeat: bool,  // Produced by holding the key down rather than pressing it
+    pub unsettled: bool,    // Arrived during or just after a burst of repeats
+}
+
+impl KeyInput {
+    pub fn presses(&self) -> usize {
+        self.repeats + 1
+    }
+}
+
+/// What a mouse event landed on
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MouseTarget {
+    Story(usize),
+    Feed(usize),
+    FeedMenu,
+    Tab(usize),
+    Ticker,
+    Preview,
+    Article,
+}
+
+/// Detects key repeat and coalesces bursts of it.
+/// Terminals using the kitty keyboard protocol report repeats directly; elsewhere a repeat is
+/// the same key arriving again within `repeat_ms`. Buffered input read in one batch shares a
+/// timestamp, so a held key that was buffered always counts as a burst.
+#[derive(Debug)]
+pub struct InputFilter {
+    repeat: Duration,
+    settle: Duration,
+    confirm_open: bool,
+    double_click: Duration,
+    last_press: Option<(KeyChord, Instant)>,
+    unsettled_until: Option<Instant>,
+    last_click: Option<(MouseTarget, Instant)>,
+}
+
+impl Default for InputFilter {
+    fn default() -> Self {
+        Self::new(&InputConfig::default())
+    }
+}
+
+impl InputFilter {
+    pub fn new(config: &InputConfig) -> Self {
+        Self {
+            repeat: Duration::from_millis(config.repeat_ms),
+            settle: Duration::from_millis(config.settle_ms),
+            confirm_open: config.confirm_open,
+            double_click: Duration::from_millis(config.double_click_ms),
+            last_press: None,
+            unsettled_until: None,
+            last_click: None,
+        }
+    }
+
+    /// Record a click. Returns true if it completes a double-click on the same target.
+    pub fn click(&mut self, target: MouseTarget, now: Instant) -> bool {
+        let double = self.last_click
+            .is_some_and(|(last, at)| last == target && now.duration_since(at) < self.double_click);
+        // A third click starts a new pair rather than making another double-click
+        self.last_click = if double { None } else { Some((target, now)) };
+        double
+    }
+
+    /// Whether opening a story should be confirmed for this input
+    pub fn confirm_open(&self, input: &KeyInput) -> bool {
+        self.confirm_open && input.unsettled
+    }
+
+    /// Filter a batch of key events read at `now`. Releases are dropped and runs of
+    /// repeats of the same key become a single input.
+    pub fn process(&mut self, keys: &[KeyEvent], now: Instant) -> Vec<KeyInput> {
+        let mut inputs: Vec<KeyInput> = Vec::new();
+
+        for key in keys {
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+
+            let chord = KeyChord::from(*key);
+            let auto_repeat = key.kind == KeyEventKind::Repeat
+                || self.last_press.is_some_and(|(last, at)| last == chord && now.duration_since(at) < self.repeat);
+            self.last_press = Some((chord, now));
+
+            if auto_repeat {
+                self.unsettled_until = Some(now + self.settle);
+            }
+            let unsettled = self.unsettled_until.is_some_and(|until| now < until);
+
+            match inputs.last_mut() {
+                Some(last) if auto_repeat && last.auto_repeat && KeyChord::from(last.key) == chord => {
+                    last.repeats += 1;
+                }
+                _ => inputs.push(KeyInput { key: *key, repeats: 0, auto_repeat, unsettled }),
+            }
+        }
+
+        inputs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn key(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    fn key_kind(c: char, kind: KeyEventKind) -> KeyEvent {
+        KeyEvent::new_with_kind(KeyCode::Char(c), KeyModifiers::NONE, kind)
+    }
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_buffered_repeats_coalesce() {
+        let mut filter = InputFilter::default();
+        let start = Instant::now();
+        let inputs = filter.process(&[key('j'); 40], start);
+
+        assert_eq!(inputs.len(), 2);
+        assert_eq!((inputs[0].presses(), inputs[0].auto_repeat, inputs[0].unsettled), (1, false, false));
+        assert_eq!((inputs[1].presses(), inputs[1].auto_repeat, inputs[1].unsettled), (39, true, true));
+    }
+
+    #[test]
+    fn test_separate_presses_are_not_repeats() {
+        let mut filter = InputFilter::default();
+        let start = Instant::now();
+        let mut inputs = filter.process(&[key('j')], start);
+        inputs.extend(filter.process(&[key('j')], start + ms(150)));
+        inputs.extend(filter.process(&[key('o')], start + ms(300)));
+
+        assert_eq!(inputs.len(), 3);
+        assert!(inputs.iter().all(|input| !input.auto_repeat && !input.unsettled));
+    }
+
+    #[test]
+    fn test_input_settles_after_a_burst() {
+        let mut filter = InputFilter::default();
+        let start = Instant::now();
+        filter.process(&[key('j'), key('j'), key('j')], start);
+
+        let during = filter.process(&[key('o')], start + ms(100));
+        assert!(during[0].unsettled && !during[0].auto_repeat);
+        assert!(filter.confirm_open(&during[0]));
+
+        let after = filter.process(&[key('o')], start + ms(700));
+        assert!(!after[0].unsettled);
+        assert!(!filter.confirm_open(&after[0]));
+    }
+
+    #[test]
+    fn test_reported_repeats_and_releases() {
+        let mut filter = InputFilter::default();
+        let start = Instant::now();
+        let inputs = filter.process(&[
+            key_kind('j', KeyEventKind::Press),
+            key_kind('j', KeyEventKind::Repeat),
+            key_kind('j', KeyEventKind::Repeat),
+            key_kind('j', KeyEventKind::Release),
+        ], start);
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[1].presses(), 2);
+
+        // A reported repeat counts even when it arrives slowly
+        let slow = filter.process(&[key_kind('j', KeyEventKind::Repeat)], start + ms(200));
+        assert!(slow[0].auto_repeat);
+    }
+
+    #[test]
+    fn test_double_click_needs_same_target() {
+        let mut filter = InputFilter::default();
+        let start = Instant::now();
+        assert!(!filter.click(MouseTarget::Story(1), start));
+        assert!(!filter.click(MouseTarget::Story(2), start + ms(100)));
+        assert!(filter.click(MouseTarget::Story(2), start + ms(200)));
+        assert!(!filter.click(MouseTarget::Story(2), start + ms(300)));
+        assert!(!filter.click(MouseTarget::Story(2), start + ms(1000)));
+    }
+
+    #[test]
+    fn test_confirmation_can_be_disabled() {
+        let mut filter = InputFilter::new(&InputConfig { confirm_open: false, ..InputConfig::default() });
+        let inputs = filter.process(&[key('j'), key('j'), key('o')], Instant::now());
+        assert!(inputs[2].unsettled);
+        assert!(!filter.confirm_open(&inputs[2]));
+    }
+}
@@ -0,0 +1,706 @@
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use crate::config::KeyBindings;
+
+/// A single key press with modifiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        // Shift is part of the character ('G') or the key (BackTab), so it's dropped to make
+        // "G", "shift-g" and what terminals report for Shift+g all compare equal
+        let code = match code {
+            KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        let modifiers = match code {
+            KeyCode::Char(_) | KeyCode::BackTab => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers: modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT) }
+    }
+
+    fn parse(token: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = token;
+        loop {
+            let lower = rest.to_ascii_lowercase();
+            let prefix = [("ctrl-", KeyModifiers::CONTROL), ("alt-", KeyModifiers::ALT), ("shift-", KeyModifiers::SHIFT)]
+                .into_iter()
+                .find(|(prefix, _)| lower.starts_with(prefix) && lower.len() > prefix.len());
+            match prefix {
+                Some((prefix, modifier)) => {
+                    modifiers |= modifier;
+                    rest = &rest[prefix.len()..];
+                }
+                None => break,
+            }
+        }
+
+        let code = match named_key(rest) {
+            Some(code) => code,
+            None => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => bail!("unknown key `{}`", token),
+                }
+            }
+        };
+
+        // Shift-Tab arrives as its own key
+        let code = match code {
+            KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            code => code,
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl From<KeyEvent> for KeyChord {
+    fn from(key: KeyEvent) -> Self {
+        Self::new(key.code, key.modifiers)
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::F(n) => write!(f, "F{}", n),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            code => {
+                let name = NAMED_KEYS.iter().find(|(_, named)| *named == code).map(|(name, _)| *name).unwrap_or("?");
+                write!(f, "{}", name)
+            }
+        }
+    }
+}
+
+// Display names first; parsing is case-insensitive and also accepts the aliases
+const NAMED_KEYS: [(&str, KeyCode); 15] = [
+    ("Enter", KeyCode::Enter),
+    ("Esc", KeyCode::Esc),
+    ("Tab", KeyCode::Tab),
+    ("Space", KeyCode::Char(' ')),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+];
+
+const KEY_ALIASES: [(&str, KeyCode); 5] = [
+    ("return", KeyCode::Enter),
+    ("escape", KeyCode::Esc),
+    ("del", KeyCode::Delete),
+    ("pgup", KeyCode::PageUp),
+    ("pgdn", KeyCode::PageDown),
+];
+
+fn named_key(name: &str) -> Option<KeyCode> {
+    if let Some(n) = name.strip_prefix(['f', 'F']).and_then(|n| n.parse::<u8>().ok()) {
+        return (1..=12).contains(&n).then_some(KeyCode::F(n));
+    }
+    NAMED_KEYS.iter()
+        .chain(KEY_ALIASES.iter())
+        .find(|(named, _)| named.eq_ignore_ascii_case(name))
+        .map(|(_, code)| *code)
+}
+
+/// One or more chords pressed in order, e.g. "gg" or "ctrl-w j"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+impl KeySequence {
+    /// Chords are separated by spaces. A token that isn't a key name or chord is read as
+    /// one key per character, so "gg" is g then g.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let mut chords = Vec::new();
+        for token in spec.split_whitespace() {
+            match KeyChord::parse(token) {
+                Ok(chord) => chords.push(chord),
+                Err(_) if !token.contains('-') => {
+                    chords.extend(token.chars().map(|c| KeyChord::new(KeyCode::Char(c), KeyModifiers::NONE)));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if chords.is_empty() {
+            bail!("empty key");
+        }
+        Ok(Self(chords))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Plain characters run together ("gg"), anything else is space separated
+        let plain = self.0.iter().all(|chord| {
+            chord.modifiers.is_empty() && matches!(chord.code, KeyCode::Char(c) if c != ' ' && c != '-')
+        });
+        let separator = if plain { "" } else { " " };
+        let chords: Vec<String> = self.0.iter().map(|chord| chord.to_string()).collect();
+        write!(f, "{}", chords.join(separator))
+    }
+}
+
+/// Keys bound to an action: a single key ("q"), a list (["j", "down"]) or nothing ("" or [])
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Keys(pub Vec<KeySequence>);
+
+impl Keys {
+    fn from_specs(specs: &[&str]) -> Self {
+        Self(specs.iter().map(|spec| KeySequence::parse(spec).expect("valid default key")).collect())
+    }
+}
+
+impl<'de> Deserialize<'de> for Keys {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Spec {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        let specs = match Spec::deserialize(deserializer)
+            .map_err(|_| serde::de::Error::custom("expected a key like \"q\" or \"ctrl-d\", or a list of keys"))?
+        {
+            Spec::One(spec) if spec.trim().is_empty() => Vec::new(),
+            Spec::One(spec) => vec![spec],
+            Spec::Many(specs) => specs,
+        };
+
+        specs.iter()
+            .map(|spec| KeySequence::parse(spec).map_err(serde::de::Error::custom))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Keys)
+    }
+}
+
+impl Serialize for Keys {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0.as_slice() {
+            [single] => serializer.serialize_str(&single.to_string()),
+            many => serializer.collect_seq(many.iter().map(|keys| keys.to_string())),
+        }
+    }
+}
+
+/// Everything a key can be bound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    Quit,
+    Back,
+    ScrollDown,
+    ScrollUp,
+    Latest,
+    ScrollBottom,
+    PageDown,
+    PageUp,
+    HalfPageDown,
+    HalfPageUp,
+    Open,
+    OpenNewTab,
+    Refresh,
+    Ticker,
+    ToggleRead,
+    MarkAllRead,
+    Bookmark,
+    Search,
+    NextMatch,
+    PreviousMatch,
+    ArchiveSearch,
+    Preview,
+    Article,
+    FeedMenu,
+    Sort,
+    DateFormat,
+    Theme,
+    ImageProtocol,
+    Help,
+    Palette,
+    Layout,
+    FocusLeft,
+    FocusRight,
+    GrowPreview,
+    ShrinkPreview,
+    MovePreview,
+    NextTab,
+    PreviousTab,
+    GoToTab(u8),
+    CloseTab,
+    OpenTab,
+    Select,
+    Close,
+}
+
+impl Action {
+    /// Name used in the config file
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Back => "back",
+            Action::ScrollDown => "scroll_down",
+            Action::ScrollUp => "scroll_up",
+            Action::Latest => "latest",
+            Action::ScrollBottom => "scroll_bottom",
+            Action::PageDown => "page_down",
+            Action::PageUp => "page_up",
+            Action::HalfPageDown => "half_page_down",
+            Action::HalfPageUp => "half_page_up",
+            Action::Open => "open",
+            Action::OpenNewTab => "open_new_tab",
+            Action::Refresh => "refresh",
+            Action::Ticker => "ticker",
+            Action::ToggleRead => "toggle_read",
+            Action::MarkAllRead => "mark_all_read",
+            Action::Bookmark => "bookmark",
+            Action::Search => "search",
+            Action::NextMatch => "next_match",
+            Action::PreviousMatch => "previous_match",
+            Action::ArchiveSearch => "archive_search",
+            Action::Preview => "preview",
+            Action::Article => "open_article",
+            Action::FeedMenu => "open_feeds",
+            Action::Sort => "sort",
+            Action::DateFormat => "date_format",
+            Action::Theme => "theme",
+            Action::ImageProtocol => "image_protocol",
+            Action::Help => "show_help",
+            Action::Palette => "command_palette",
+            Action::Layout => "layout",
+            Action::FocusLeft => "focus_left",
+            Action::FocusRight => "focus_right",
+            Action::GrowPreview => "grow_preview",
+            Action::ShrinkPreview => "shrink_preview",
+            Action::MovePreview => "move_preview",
+            Action::NextTab => "next_tab",
+            Action::PreviousTab => "previous_tab",
+            Action::GoToTab(n) => TAB_NAMES.get(n as usize - 1).copied().unwrap_or("tab"),
+            Action::CloseTab => "close_tab",
+            Action::OpenTab => "open_tab",
+            Action::Select => "select",
+            Action::Close => "close",
+        }
+    }
+
+    /// Description shown in the help overlay
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "Quit",
+            Action::Back => "Clear search, or quit",
+            Action::ScrollDown => "Scroll down",
+            Action::ScrollUp => "Scroll up",
+            Action::Latest => "Jump to top",
+            Action::ScrollBottom => "Jump to bottom",
+            Action::PageDown => "Page down",
+            Action::PageUp => "Page up",
+            Action::HalfPageDown => "Half a page down",
+            Action::HalfPageUp => "Half a page up",
+            Action::Open => "Open article in browser",
+            Action::OpenNewTab => "Open in new tab",
+            Action::Refresh => "Refresh news feed",
+            Action::Ticker => "Jump to ticker article",
+            Action::ToggleRead => "Toggle read / unread",
+            Action::MarkAllRead => "Mark all as read",
+            Action::Bookmark => "Save / unsave story",
+            Action::Search => "Search",
+            Action::NextMatch => "Next match",
+            Action::PreviousMatch => "Previous match",
+            Action::ArchiveSearch => "Search archive of all past stories",
+            Action::Preview => "Toggle preview pane",
+            Action::Article => "Open article view",
+            Action::FeedMenu => "Open feed selector",
+            Action::Sort => "Cycle sort order",
+            Action::DateFormat => "Toggle date format",
+            Action::Theme => "Cycle theme",
+            Action::ImageProtocol => "Cycle image protocol",
+            Action::Help => "Toggle this help menu",
+            Action::Palette => "Command palette",
+            Action::Layout => "Toggle three-pane layout",
+            Action::FocusLeft => "Focus pane on the left",
+            Action::FocusRight => "Focus pane on the right",
+            Action::GrowPreview => "Widen the preview",
+            Action::ShrinkPreview => "Narrow the preview",
+            Action::MovePreview => "Move the preview right / bottom",
+            Action::NextTab => "Next tab",
+            Action::PreviousTab => "Previous tab",
+            Action::GoToTab(n) => TAB_DESCRIPTIONS.get(n as usize - 1).copied().unwrap_or("Go to tab"),
+            Action::CloseTab => "Close tab",
+            Action::OpenTab => "Open in a new tab",
+            Action::Select => "Select",
+            Action::Close => "Close",
+        }
+    }
+}
+
+const TAB_NAMES: [&str; 9] = ["tab_1", "tab_2", "tab_3", "tab_4", "tab_5", "tab_6", "tab_7", "tab_8", "tab_9"];
+const TAB_DESCRIPTIONS: [&str; 9] = [
+    "Go to tab 1", "Go to tab 2", "Go to tab 3", "Go to tab 4", "Go to tab 5",
+    "Go to tab 6", "Go to tab 7", "Go to tab 8", "Go to tab 9",
+];
+
+/// Which set of bindings applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyMode {
+    Normal,
+    FeedMenu,
+    Article,
+    Help,
+}
+
+impl KeyMode {
+    pub const ALL: [KeyMode; 4] = [KeyMode::Normal, KeyMode::FeedMenu, KeyMode::Article, KeyMode::Help];
+
+    /// Table under [keybindings] (normal mode bindings sit in [keybindings] itself)
+    pub fn table(self) -> Option<&'static str> {
+        match self {
+            KeyMode::Normal => None,
+            KeyMode::FeedMenu => Some("feed_menu"),
+            KeyMode::Article => Some("article"),
+            KeyMode::Help => Some("help"),
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            KeyMode::Normal => "the story list",
+            KeyMode::FeedMenu => "the feed menu",
+            KeyMode::Article => "the article view",
+            KeyMode::Help => "the help menu",
+        }
+    }
+
+    /// Actions available in this mode with their help section and default keys, in help order
+    pub fn defaults(self) -> &'static [(&'static str, Action, &'static [&'static str])] {
+        match self {
+            KeyMode::Normal => &[
+                ("Navigation", Action::ScrollDown, &["j", "Down"]),
+                ("Navigation", Action::ScrollUp, &["k", "Up"]),
+                ("Navigation", Action::ScrollBottom, &["G"]),
+                ("Navigation", Action::Latest, &["l"]),
+                ("Navigation", Action::PageDown, &["PageDown", "ctrl-f"]),
+                ("Navigation", Action::PageUp, &["PageUp", "ctrl-b"]),
+                ("Navigation", Action::HalfPageDown, &["ctrl-d"]),
+                ("Navigation", Action::HalfPageUp, &["ctrl-u"]),
+                ("Navigation", Action::Search, &["/"]),
+                ("Navigation", Action::NextMatch, &["n"]),
+                ("Navigation", Action::PreviousMatch, &["N"]),
+                ("Navigation", Action::ArchiveSearch, &["S"]),
+                ("Actions", Action::Open, &["o"]),
+                ("Actions", Action::OpenNewTab, &["O"]),
+                ("Actions", Action::Ticker, &["Space"]),
+                ("Actions", Action::Refresh, &["r"]),
+                ("Actions", Action::ToggleRead, &["m"]),
+                ("Actions", Action::MarkAllRead, &["M"]),
+                ("Actions", Action::Bookmark, &["b"]),
+                ("Views", Action::Preview, &["Tab"]),
+                ("Views", Action::Article, &["a", "Enter"]),
+                ("Views", Action::FeedMenu, &["f"]),
+                ("Views", Action::Layout, &["L"]),
+                ("Views", Action::FocusLeft, &["Left", "ctrl-h"]),
+                ("Views", Action::FocusRight, &["Right", "ctrl-l"]),
+                ("Views", Action::GrowPreview, &["+", "="]),
+                ("Views", Action::ShrinkPreview, &["-"]),
+                ("Views", Action::MovePreview, &["P"]),
+                ("Tabs", Action::NextTab, &["gt"]),
+                ("Tabs", Action::PreviousTab, &["gT"]),
+                ("Tabs", Action::GoToTab(1), &["alt-1"]),
+                ("Tabs", Action::GoToTab(2), &["alt-2"]),
+                ("Tabs", Action::GoToTab(3), &["alt-3"]),
+                ("Tabs", Action::GoToTab(4), &["alt-4"]),
+                ("Tabs", Action::GoToTab(5), &["alt-5"]),
+                ("Tabs", Action::GoToTab(6), &["alt-6"]),
+                ("Tabs", Action::GoToTab(7), &["alt-7"]),
+                ("Tabs", Action::GoToTab(8), &["alt-8"]),
+                ("Tabs", Action::GoToTab(9), &["alt-9"]),
+                ("Tabs", Action::CloseTab, &["X"]),
+                ("Settings", Action::Sort, &["s"]),
+                ("Settings", Action::DateFormat, &["t"]),
+                ("Settings", Action::Theme, &["T"]),
+                ("Settings", Action::ImageProtocol, &["p"]),
+                ("Other", Action::Quit, &["q"]),
+                ("Other", Action::Back, &["Esc"]),
+                ("Other", Action::Help, &["?"]),
+                ("Other", Action::Palette, &[":", "ctrl-p"]),
+            ],
+            KeyMode::FeedMenu => &[
+                ("Feed menu", Action::ScrollDown, &["j", "Down"]),
+                ("Feed menu", Action::ScrollUp, &["k", "Up"]),
+                ("Feed menu", Action::Select, &["Enter"]),
+                ("Feed menu", Action::OpenTab, &["t"]),
+                ("Feed menu", Action::Close, &["f", "Esc"]),
+            ],
+            KeyMode::Article => &[
+                ("Article view", Action::ScrollDown, &["j", "Down"]),
+                ("Article view", Action::ScrollUp, &["k", "Up"]),
+                ("Article view", Action::PageDown, &["PageDown", "ctrl-f"]),
+                ("Article view", Action::PageUp, &["PageUp", "ctrl-b"]),
+                ("Article view", Action::HalfPageDown, &["ctrl-d"]),
+                ("Article view", Action::HalfPageUp, &["ctrl-u"]),
+                ("Article view", Action::Latest, &["Home", "gg"]),
+                ("Article view", Action::ScrollBottom, &["End", "G"]),
+                ("Article view", Action::Search, &["/"]),
+                ("Article view", Action::NextMatch, &["n"]),
+                ("Article view", Action::PreviousMatch, &["N"]),
+                ("Article view", Action::Open, &["o"]),
+                ("Article view", Action::Palette, &[":", "ctrl-p"]),
+                ("Article view", Action::FocusLeft, &["Left", "ctrl-h"]),
+                ("Article view", Action::FocusRight, &["Right", "ctrl-l"]),
+                ("Article view", Action::GrowPreview, &["+", "="]),
+                ("Article view", Action::ShrinkPreview, &["-"]),
+                ("Article view", Action::Close, &["Enter", "Tab", "Esc"]),
+            ],
+            KeyMode::Help => &[
+                ("Help", Action::Close, &["?", "Esc"]),
+            ],
+        }
+    }
+
+    /// Find an action available in this mode by its config name
+    pub fn action(self, name: &str) -> Option<Action> {
+        self.defaults().iter().map(|(_, action, _)| *action).find(|action| action.name() == name)
+    }
+
+    pub fn default_keys(self) -> impl Iterator<Item = (Action, Keys)> {
+        self.defaults().iter().map(|(_, action, keys)| (*action, Keys::from_specs(keys)))
+    }
+}
+
+/// An action and the keys bound to it
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub section: &'static str,
+    pub action: Action,
+    pub keys: Keys,
+}
+
+/// Result of looking up the keys pressed so far
+#[derive(Debug, PartialEq)]
+pub enum Lookup {
+    Action(Action),
+    Pending,  // A prefix of a longer sequence, wait for more keys
+    None,
+}
+
+/// Bindings for every mode, resolved from config over the defaults
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    modes: Vec<(KeyMode, Vec<Binding>)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_bindings(&KeyBindings::default())
+    }
+}
+
+impl Keymap {
+    /// Build from config. Actions that aren't configured keep their default keys.
+    pub fn from_bindings(bindings: &KeyBindings) -> Self {
+        let modes = KeyMode::ALL
+            .into_iter()
+            .map(|mode| {
+                let configured = bindings.mode(mode);
+                let mode_bindings = mode.defaults()
+                    .iter()
+                    .map(|(section, action, defaults)| Binding {
+                        section,
+                        action: *action,
+                        keys: configured.get(action.name()).cloned().unwrap_or_else(|| Keys::from_specs(defaults)),
+                    })
+                    .collect();
+                (mode, mode_bindings)
+            })
+            .collect();
+        Self { modes }
+    }
+
+    pub fn bindings(&self, mode: KeyMode) -> &[Binding] {
+        self.modes.iter().find(|(m, _)| *m == mode).map(|(_, b)| b.as_slice()).unwrap_or(&[])
+    }
+
+    /// Keys bound to an action, in the order they were configured
+    pub fn keys(&self, mode: KeyMode, action: Action) -> &[KeySequence] {
+        self.bindings(mode).iter().find(|b| b.action == action).map(|b| b.keys.0.as_slice()).unwrap_or(&[])
+    }
+
+    /// Look up keys pressed so far. A complete match wins over a longer sequence it starts.
+    pub fn lookup(&self, mode: KeyMode, pressed: &[KeyChord]) -> Lookup {
+        let mut pending = false;
+        for binding in self.bindings(mode) {
+            for keys in &binding.keys.0 {
+                if keys.0 == pressed {
+                    return Lookup::Action(binding.action);
+                }
+                pending |= keys.0.len() > pressed.len() && keys.0.starts_with(pressed);
+            }
+        }
+        if pending { Lookup::Pending } else { Lookup::None }
+    }
+
+    /// Keys bound to more than one action, and sequences hidden by a shorter binding
+    pub fn conflicts(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        for (mode, bindings) in &self.modes {
+            let all: Vec<(&KeySequence, Action)> = bindings.iter()
+                .flat_map(|b| b.keys.0.iter().map(move |keys| (keys, b.action)))
+                .collect();
+            let place = match mode {
+                KeyMode::Normal => String::new(),
+                mode => format!(" in {}", mode.title()),
+            };
+
+            let mut reported: Vec<&KeySequence> = Vec::new();
+            for (i, (keys, action)) in all.iter().enumerate() {
+                if reported.contains(keys) {
+                    continue;
+                }
+                let mut actions = vec![*action];
+                for (other_keys, other_action) in &all[i + 1..] {
+                    if other_keys == keys && !actions.contains(other_action) {
+                        actions.push(*other_action);
+                    }
+                }
+                if actions.len() > 1 {
+                    reported.push(keys);
+                    actions.sort();
+                    let names: Vec<&str> = actions.iter().map(|action| action.name()).collect();
+                    warnings.push(format!("'{}' is bound to more than one action{}: {}", keys, place, names.join(", ")));
+                }
+            }
+
+            for (keys, action) in &all {
+                for (other_keys, other_action) in &all {
+                    if other_keys.0.len() > keys.0.len() && other_keys.0.starts_with(&keys.0) {
+                        warnings.push(format!(
+                            "'{}' ({}) can never be typed because '{}' ({}) comes first{}",
+                            other_keys, other_action.name(), keys, action.name(), place
+                        ));
+                    }
+                }
+            }
+        }
+
+        warnings
+    }
+
+    /// The full keymap in config form
+    pub fn to_bindings(&self) -> KeyBindings {
+        let mut bindings = KeyBindings::empty();
+        for (mode, mode_bindings) in &self.modes {
+            let table = bindings.mode_mut(*mode);
+            for binding in mode_bindings {
+                table.insert(binding.action.name().to_string(), binding.keys.clone());
+            }
+        }
+        bindings
+    }
+
+    /// Short label for the first key bound to an action, for hints
+    pub fn hint(&self, mode: KeyMode, action: Action) -> Option<String> {
+        self.keys(mode, action).first().map(|keys| keys.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chord(code: KeyCode, modifiers: KeyModifiers) -> KeyChord {
+        KeyChord::new(code, modifiers)
+    }
+
+    fn char_key(c: char) -> KeyChord {
+        chord(KeyCode::Char(c), KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_parse_chords_and_sequences() {
+        assert_eq!(KeySequence::parse("ctrl-d").unwrap().0, vec![chord(KeyCode::Char('d'), KeyModifiers::CONTROL)]);
+        assert_eq!(KeySequence::parse("gg").unwrap().0, vec![char_key('g'), char_key('g')]);
+        assert_eq!(KeySequence::parse("Ctrl-w j").unwrap().0, vec![
+            chord(KeyCode::Char('w'), KeyModifiers::CONTROL),
+            char_key('j'),
+        ]);
+        assert_eq!(KeySequence::parse("esc").unwrap().0, vec![chord(KeyCode::Esc, KeyModifiers::NONE)]);
+        assert_eq!(KeySequence::parse("pgdn").unwrap().0, vec![chord(KeyCode::PageDown, KeyModifiers::NONE)]);
+        assert_eq!(KeySequence::parse("alt-F5").unwrap().0, vec![chord(KeyCode::F(5), KeyModifiers::ALT)]);
+        assert!(KeySequence::parse("ctrl-").is_err());
+        assert!(KeySequence::parse("hyper-x").is_err());
+        assert!(KeySequence::parse("  ").is_err());
+    }
+
+    #[test]
+    fn test_shift_matches_terminal_events() {
+        // Terminals report Shift+g as 'G' with SHIFT, and Shift+Tab as BackTab with SHIFT
+        assert_eq!(KeySequence::parse("G").unwrap().0[0], chord(KeyCode::Char('G'), KeyModifiers::SHIFT));
+        assert_eq!(KeySequence::parse("shift-g").unwrap().0[0], char_key('G'));
+        assert_eq!(KeySequence::parse("shift-tab").unwrap().0[0], chord(KeyCode::BackTab, KeyModifiers::SHIFT));
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for spec in ["gg", "Ctrl-d", "Space", "Shift-Tab", "Ctrl-w j", "F2", "Alt-Enter", "-"] {
+            let keys = KeySequence::parse(spec).unwrap();
+            assert_eq!(keys.to_string(), spec);
+            assert_eq!(KeySequence::parse(&keys.to_string()).unwrap(), keys);
+        }
+    }
+
+    #[test]
+    fn test_lookup_sequences() {
+        let mut bindings = KeyBindings::default();
+        bindings.normal.insert(String::from("latest"), Keys(vec![KeySequence::parse("gg").unwrap()]));
+        let keymap = Keymap::from_bindings(&bindings);
+
+        assert_eq!(keymap.lookup(KeyMode::Normal, &[char_key('g')]), Lookup::Pending);
+        assert_eq!(keymap.lookup(KeyMode::Normal, &[char_key('g'), char_key('g')]), Lookup::Action(Action::Latest));
+        assert_eq!(keymap.lookup(KeyMode::Normal, &[char_key('g'), char_key('x')]), Lookup::None);
+        assert_eq!(keymap.lookup(KeyMode::Normal, &[char_key('l')]), Lookup::None);
+        assert_eq!(keymap.lookup(KeyMode::Article, &[char_key('j')]), Lookup::Action(Action::ScrollDown));
+    }
+
+    #[test]
+    fn test_default_keymap_has_no_conflicts() {
+        assert!(Keymap::default().conflicts().is_empty(), "{:?}", Keymap::default().conflicts());
+    }
+
+    #[test]
+    fn test_reports_hidden_sequences() {
+        let mut bindings = KeyBindings::default();
+        bindings.normal.insert(String::from("latest"), Keys(vec![KeySequence::parse("zz").unwrap()]));
+        bindings.normal.insert(String::from("refresh"), Keys(vec![KeySequence::parse("z").unwrap()]));
+        let conflicts = Keymap::from_bindings(&bindings).conflicts();
+        assert_eq!(conflicts, vec![String::from("'zz' (latest) can never be typed because 'z' (refresh) comes first")]);
+    }
+}
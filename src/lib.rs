@@ -1,12 +1,25 @@
 pub mod app;
 pub mod api;
+pub mod archive;
 pub mod article_fetcher;
+pub mod bookmarks;
 pub mod cache;
 pub mod cli;
+pub mod color_depth;
 pub mod config;
 pub mod date_utils;
 pub mod events;
 pub mod feeds;
 pub mod image_cache;
+pub mod input;
+pub mod keymap;
+pub mod palette;
+pub mod paths;
+pub mod read_state;
+pub mod reader;
+pub mod reading_positions;
+pub mod session;
+pub mod tabs;
+pub mod text_layout;
 pub mod theme;
 pub mod ui;
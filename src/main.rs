@@ -3,43 +3,101 @@ use bbc_news_cli::{app, api, cli, config, events, theme, ui};
 use anyhow::Result;
 use clap::Parser;
 use crossterm::{
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+        PushKeyboardEnhancementFlags,
+    },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{disable_raw_mode, enable_raw_mode, supports_keyboard_enhancement, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
+use std::sync::mpsc;
 
 use app::App;
+use bbc_news_cli::input::InputFilter;
+use bbc_news_cli::keymap::Keymap;
+use bbc_news_cli::session::Session;
 
 fn main() -> Result<()> {
     // Parse CLI arguments
     let cli_args = cli::Cli::parse();
 
+    // Move files from the old ~/.bbcli layout before anything reads them
+    for message in bbc_news_cli::paths::migrate_legacy_layout() {
+        eprintln!("bbcli: {}", message);
+    }
+
+    // Config commands must work even when the config file is broken
+    if let Some(cli::Commands::Config { action }) = cli_args.command {
+        return cli::run_config(action.unwrap_or(cli::ConfigCommands::Check));
+    }
+
+    // Load configuration, refusing to start on a broken file rather than silently using defaults
+    let loaded = config::load_config()?;
+    let config = loaded.config;
+
+    // Upgrade old cache entries, then enforce size and age limits before anything reads the cache
+    if let Ok(cache) = bbc_news_cli::cache::Cache::new() {
+        let _ = cache.migrate();
+        let _ = cache.prune(config.cache.max_age()?, config.cache.max_bytes()?);
+    }
+
     // If any subcommand is provided, run CLI mode
     if cli_args.command.is_some() || cli_args.feed.is_some() {
-        return cli::run_cli(cli_args);
+        for warning in &loaded.warnings {
+            eprintln!("bbcli: config warning: {}", warning);
+        }
+        return cli::run_cli(cli_args, &config);
     }
 
     // Otherwise, launch TUI
-    run_tui()
+    run_tui(config, loaded.themes, &loaded.warnings)
 }
 
-fn run_tui() -> Result<()> {
-    // Load configuration
-    let config = config::load_config().unwrap_or_default();
+fn run_tui(config: config::Config, themes: Vec<theme::Theme>, config_warnings: &[String]) -> Result<()> {
 
     // Get theme from config
-    let theme = theme::Theme::from_name(&config.theme);
+    let theme = theme::Theme::from_name(&config.theme, &themes);
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen)?;
+    // Terminals with the kitty keyboard protocol report key repeat, which makes held keys
+    // easy to tell apart from separate presses
+    let keyboard_enhancement = matches!(supports_keyboard_enhancement(), Ok(true));
+    if keyboard_enhancement {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+            )
+        )?;
+    }
+    if config.input.mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app state with theme
     let mut app = App::new(theme);
+    app.set_themes(themes, config.color_depth.detect());
+    app.read_state = bbc_news_cli::read_state::ReadState::load();
+    app.bookmarks = bbc_news_cli::bookmarks::Bookmarks::load();
+    app.reading_positions = bbc_news_cli::reading_positions::ReadingPositions::load();
+    app.apply_display_config(&config.display);
+    app.keymap = Keymap::from_bindings(&config.keybindings);
+    app.input = InputFilter::new(&config.input);
+    app.apply_layout_config(&config.layout);
+    app.reader = config.reader.clone();
+
+    // Pick up where the last run left off
+    let session = config.session.restore.then(Session::load).flatten();
+    if let Some(session) = &session {
+        session.restore(&mut app);
+    }
 
     // Fetch initial data (both ticker and main feed)
     if let Err(e) = fetch_ticker_data(&mut app) {
@@ -49,10 +107,27 @@ fn run_tui() -> Result<()> {
         app.set_error(format!("{:#}", e));
     }
 
+    if let Some(session) = &session {
+        // The article is wrapped to the reading pane to find the scroll position
+        app.article_view = ui::article_view(&app, terminal.size()?);
+        session.restore_position(&mut app);
+    }
+
+    app.set_config_warnings(config_warnings);
+
     // Run the app
-    let result = run_app(&mut terminal, &mut app, &config);
+    let result = run_app(&mut terminal, &mut app, config);
+
+    // Saved even when restore is off, so `bbcli config save-defaults` has something to save
+    let _ = Session::capture(&app).save();
+    app.remember_article_position();
 
     // Restore terminal
+    if keyboard_enhancement {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
+    // Whatever the config says now, mouse capture must not outlive the app
+    execute!(terminal.backend_mut(), DisableMouseCapture)?;
     disable_raw_mode()?;
     execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
     terminal.show_cursor()?;
@@ -67,11 +142,15 @@ fn run_tui() -> Result<()> {
 fn run_app(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     app: &mut App,
-    config: &config::Config,
+    mut config: config::Config,
 ) -> Result<()> {
     // Initial draw
     terminal.draw(|f| ui::render(f, app))?;
 
+    let mut config_watcher = config::get_config_path().ok().map(config::ConfigWatcher::new);
+    let tab_refresher = TabRefresher::new();
+    let cached_feeds = load_cached_feeds();
+
     loop {
         // Track state before event handling to detect changes
         let prev_selected = app.selected;
@@ -88,9 +167,42 @@ fn run_app(
         let prev_is_fetching_article = app.is_fetching_article;
         let prev_sort_order = app.sort_order.clone();
         let prev_offline = app.is_offline;
+        let prev_search_query = app.search_query.clone();
+        let prev_archive_query = app.archive_query.clone();
+        let prev_read_count = app.read_state.len();
+        let prev_bookmark_count = app.bookmarks.len();
+        let prev_theme = app.theme.clone();
+        let prev_config_message = app.config_message.clone();
+        let prev_pending_keys = app.pending_keys.len();
+        let prev_pending_count = app.pending_count;
+        let prev_confirm_open = app.confirm_open;
+        let prev_palette = app.palette.as_ref().map(|palette| (palette.query.clone(), palette.selected));
+        let prev_layout = app.layout.clone();
+        let prev_reader = app.reader.clone();
+        let prev_focus = app.focus;
+        let prev_tabs = app.tab_names().join("\n");
+        let prev_active_tab = app.active_tab;
+
+        // Pick up edits to the config file without a restart
+        if config_watcher.as_mut().is_some_and(|watcher| watcher.changed()) {
+            let mouse = config.input.mouse;
+            reload_config(app, &mut config);
+            if config.input.mouse != mouse {
+                if config.input.mouse {
+                    execute!(terminal.backend_mut(), EnableMouseCapture)?;
+                } else {
+                    execute!(terminal.backend_mut(), DisableMouseCapture)?;
+                }
+            }
+        }
+
+        // Page moves need the current size, which may have changed since the last key
+        let size = terminal.size()?;
+        app.list_height = ui::list_height(app, size);
+        app.article_view = ui::article_view(app, size);
 
         // Check for auto-refresh (every 5 minutes)
-        let mut action = events::handle_events(app, config)?;
+        let mut action = events::handle_events(app)?;
 
         if app.check_auto_refresh() && matches!(action, events::AppAction::None) {
             action = events::AppAction::Refresh;
@@ -129,6 +241,18 @@ fn run_app(
             events::AppAction::None => {}
         }
 
+        // Other tabs refresh on their own schedule, in the background
+        let tabs_refreshed = tab_refresher.poll(app);
+
+        // Counts from the cache fill in feeds that haven't been fetched this run
+        let mut counts_loaded = false;
+        while let Ok((url, stories)) = cached_feeds.try_recv() {
+            if !app.feed_unread_counts.contains_key(&url) {
+                app.record_unread_count(&url, &stories);
+                counts_loaded = true;
+            }
+        }
+
         // Update ticker rotation and check if clock should update
         let clock_tick = app.tick();
 
@@ -147,6 +271,23 @@ fn run_app(
             || prev_is_fetching_article != app.is_fetching_article
             || prev_sort_order != app.sort_order
             || prev_offline != app.is_offline
+            || prev_search_query != app.search_query
+            || prev_archive_query != app.archive_query
+            || prev_read_count != app.read_state.len()
+            || prev_bookmark_count != app.bookmarks.len()
+            || prev_theme != app.theme
+            || prev_config_message != app.config_message
+            || prev_pending_keys != app.pending_keys.len()
+            || prev_pending_count != app.pending_count
+            || prev_confirm_open != app.confirm_open
+            || prev_palette != app.palette.as_ref().map(|palette| (palette.query.clone(), palette.selected))
+            || prev_layout != app.layout
+            || prev_reader != app.reader
+            || prev_focus != app.focus
+            || prev_tabs != app.tab_names().join("\n")
+            || prev_active_tab != app.active_tab
+            || tabs_refreshed
+            || counts_loaded
             || clock_tick  // Redraw when clock ticks (approximately every second)
             || matches!(action, events::AppAction::Refresh | events::AppAction::FeedChanged | events::AppAction::Resize);
 
@@ -162,35 +303,148 @@ fn run_app(
     Ok(())
 }
 
+/// Re-read the config file and apply it. A broken file keeps the current settings.
+fn reload_config(app: &mut App, config: &mut config::Config) {
+    match config::load_config() {
+        Ok(loaded) => {
+            // Edits to the current theme's colours apply straight away
+            app.set_themes(loaded.themes, loaded.config.color_depth.detect());
+            // Only switch theme when the file changed it, so a theme picked with T survives other edits
+            if loaded.config.theme != config.theme {
+                app.set_theme(&loaded.config.theme);
+            }
+            if loaded.config.display != config.display {
+                app.apply_display_config(&loaded.config.display);
+            }
+            app.keymap = Keymap::from_bindings(&loaded.config.keybindings);
+            if loaded.config.input != config.input {
+                app.input = InputFilter::new(&loaded.config.input);
+            }
+            if loaded.config.layout != config.layout {
+                // Keep a layout picked with L unless the file changed the mode
+                let mut layout = loaded.config.layout.clone();
+                if layout.mode == config.layout.mode {
+                    layout.mode = app.layout.mode;
+                }
+                app.apply_layout_config(&layout);
+            }
+            app.reader = loaded.config.reader.clone();
+            app.set_config_warnings(&loaded.warnings);
+            *config = loaded.config;
+        }
+        Err(e) => app.set_config_error(&e),
+    }
+}
+
 fn fetch_data(app: &mut App) -> Result<()> {
+    // Saved pseudo-feed is served from bookmarks, never the network
+    if app.current_feed.is_saved() {
+        app.is_offline = false;
+        app.update_stories(app.bookmarks.stories());
+        return Ok(());
+    }
+
+    // Archive search results come from the local archive
+    if app.current_feed.is_archive() {
+        let query = bbc_news_cli::archive::ArchiveQuery {
+            text: app.archive_query.clone(),
+            ..Default::default()
+        };
+        let results = bbc_news_cli::archive::Archive::open()?.search(&query)?;
+        app.update_stories(results.into_iter().map(|r| r.story).collect());
+        return Ok(());
+    }
+
     // Fetch stories from current feed with cache support
-    let feed_url = &app.current_feed.url;
-
-    // First try network fetch directly
-    match api::fetch_stories(feed_url) {
-        Ok(stories) => {
-            // Network is available
-            app.is_offline = false;
-            app.update_stories(stories);
-            Ok(())
+    let fetched = fetch_feed(&app.current_feed.url)?;
+    app.is_offline = fetched.is_offline;
+    app.offline_since = fetched.offline_since;
+    app.update_stories(fetched.stories);
+    Ok(())
+}
+
+/// Stories for a feed, from the cache when the network is down
+struct FetchedFeed {
+    stories: Vec<bbc_news_cli::app::NewsStory>,
+    is_offline: bool,
+    offline_since: Option<chrono::DateTime<chrono::Local>>,  // When the cached copy was fetched
+}
+
+fn fetch_feed(feed_url: &str) -> Result<FetchedFeed> {
+    let (stories, source) = api::fetch_feed(feed_url)?;
+    // An expired cached copy means the network is down
+    let offline_since = match source {
+        api::FeedSource::Stale(age) => Some(chrono::Local::now() - chrono::Duration::seconds(age as i64)),
+        api::FeedSource::Cache | api::FeedSource::Network => None,
+    };
+    Ok(FetchedFeed { stories, is_offline: offline_since.is_some(), offline_since })
+}
+
+/// Fetches feeds for background tabs on worker threads, so the UI never waits on the network
+struct TabRefresher {
+    sender: mpsc::Sender<(String, Result<FetchedFeed>)>,
+    receiver: mpsc::Receiver<(String, Result<FetchedFeed>)>,
+}
+
+impl TabRefresher {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self { sender, receiver }
+    }
+
+    /// Start fetches for the tabs that are due and store any that have finished.
+    /// Returns true if a tab got new stories.
+    fn poll(&self, app: &mut App) -> bool {
+        for index in app.tabs_to_refresh() {
+            let tab = &mut app.tabs[index];
+            // Counts as refreshed from the start, so a slow or failed fetch isn't started
+            // again until the next refresh. A tab that never loads shows the error when it's
+            // switched to.
+            tab.last_refresh_time = Some(std::time::Instant::now());
+            let url = tab.feed.url.clone();
+            let sender = self.sender.clone();
+            std::thread::spawn(move || {
+                let fetched = fetch_feed(&url);
+                let _ = sender.send((url, fetched));
+            });
         }
-        Err(e) => {
-            // Network failed, try to use cache
-            let cache = bbc_news_cli::cache::Cache::new().ok();
-            if let Some(ref cache) = cache {
-                if let Some(cached_stories) = cache.load_feed_offline(feed_url) {
-                    // We have cached data - we're offline
-                    app.is_offline = true;
-                    app.update_stories(cached_stories);
-                    return Ok(());
+
+        let mut updated = false;
+        while let Ok((url, fetched)) = self.receiver.try_recv() {
+            let Ok(fetched) = fetched else { continue };
+            // The tab may have been closed or switched to while it was fetching
+            let active_tab = app.active_tab;
+            for (i, tab) in app.tabs.iter_mut().enumerate() {
+                if i == active_tab || tab.feed.url != url {
+                    continue;
                 }
+                tab.is_offline = fetched.is_offline;
+                tab.offline_since = fetched.offline_since;
+                tab.update_stories(fetched.stories.clone());
+                updated = true;
             }
-            // No cache available either
-            Err(e)
+            app.record_unread_count(&url, &fetched.stories);
         }
+        updated
     }
 }
 
+/// Read the cached copy of every menu feed on a worker thread, for the unread counts
+fn load_cached_feeds() -> mpsc::Receiver<(String, Vec<bbc_news_cli::app::NewsStory>)> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let Ok(cache) = bbc_news_cli::cache::Cache::new() else { return };
+        for feed in bbc_news_cli::feeds::get_menu_feeds() {
+            if let Some(stories) = cache.load_feed_offline(&feed.url) {
+                if sender.send((feed.url, stories)).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    receiver
+}
+
 fn fetch_ticker_data(app: &mut App) -> Result<()> {
     // Always fetch Top Stories for ticker
     const TOP_STORIES_URL: &str = "https://feeds.bbci.co.uk/news/rss.xml";
@@ -0,0 +1,269 @@
+use crate::app::{App, ImageProtocol, SortOrder};
+use crate::feeds::{get_menu_feeds, Feed};
+use crate::keymap::{Action, KeyMode};
+use crate::theme::ThemeName;
+
+/// Something the palette can run
+#[derive(Debug, Clone)]
+pub enum Command {
+    Action(KeyMode, Action),
+    Sort(SortOrder),
+    Theme(ThemeName),
+    ImageProtocol(ImageProtocol),
+    Feed(Feed),
+    Story(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct PaletteItem {
+    pub command: Command,
+    pub label: String,
+    pub hint: String,  // Key binding, or what kind of item it is
+    search_text: String,
+}
+
+impl PaletteItem {
+    fn new(command: Command, label: String, hint: String, keywords: &str) -> Self {
+        let mut search_text = format!("{} {}", label, keywords);
+        // Let "toggle theme" find "Cycle theme" and so on
+        for (word, synonyms) in SYNONYMS {
+            if search_text.to_lowercase().split(|c: char| !c.is_alphanumeric()).any(|w| w == word) {
+                search_text.push(' ');
+                search_text.push_str(synonyms);
+            }
+        }
+        Self { command, label, hint, search_text }
+    }
+}
+
+const SYNONYMS: [(&str, &str); 6] = [
+    ("cycle", "toggle switch change"),
+    ("toggle", "switch show hide"),
+    ("jump", "go"),
+    ("open", "show view read"),
+    ("search", "find filter"),
+    ("save", "bookmark"),
+];
+
+/// Fuzzy finder over every action, setting, feed and story
+#[derive(Debug)]
+pub struct Palette {
+    pub query: String,
+    pub selected: usize,  // Position in the matches
+    items: Vec<PaletteItem>,
+    matches: Vec<usize>,
+}
+
+impl Palette {
+    /// Build the item list from the app's current state.
+    /// Actions of the current view come first, then the story list's.
+    pub fn new(app: &App, mode: KeyMode) -> Self {
+        let mut items = Vec::new();
+
+        let mut modes = vec![mode];
+        if mode != KeyMode::Normal {
+            modes.push(KeyMode::Normal);
+        }
+        let mut listed = Vec::new();
+        for mode in modes {
+            for binding in app.keymap.bindings(mode) {
+                if matches!(binding.action, Action::Palette | Action::Select) || listed.contains(&binding.action) {
+                    continue;
+                }
+                listed.push(binding.action);
+                let label = match mode {
+                    KeyMode::Normal => binding.action.description().to_string(),
+                    _ => format!("{}: {}", binding.section, binding.action.description()),
+                };
+                let hint = app.keymap.hint(mode, binding.action).unwrap_or_default();
+                let keywords = binding.action.name().replace('_', " ");
+                items.push(PaletteItem::new(Command::Action(mode, binding.action), label, hint, &keywords));
+            }
+        }
+
+        for order in SortOrder::ALL {
+            let hint = current_hint(app.sort_order == order);
+            items.push(PaletteItem::new(Command::Sort(order.clone()), format!("Sort: {}", order.name()), hint, ""));
+        }
+        for theme in &app.themes {
+            let hint = current_hint(app.theme.name == theme.name);
+            let label = format!("Theme: {}", theme.name);
+            items.push(PaletteItem::new(Command::Theme(theme.config_name()), label, hint, ""));
+        }
+        for protocol in ImageProtocol::ALL {
+            let hint = current_hint(app.image_protocol == protocol);
+            let label = format!("Image protocol: {}", protocol.name());
+            items.push(PaletteItem::new(Command::ImageProtocol(protocol.clone()), label, hint, ""));
+        }
+
+        for feed in get_menu_feeds() {
+            let hint = String::from(if feed.url == app.current_feed.url { "current feed" } else { "feed" });
+            items.push(PaletteItem::new(Command::Feed(feed.clone()), format!("Feed: {}", feed.name), hint, ""));
+        }
+
+        for (index, story) in app.stories.iter().enumerate() {
+            items.push(PaletteItem::new(Command::Story(index), story.title.clone(), format!("story {}", index + 1), ""));
+        }
+
+        let mut palette = Self { query: String::new(), selected: 0, items, matches: Vec::new() };
+        palette.update_matches();
+        palette
+    }
+
+    pub fn push(&mut self, c: char) {
+        self.query.push(c);
+        self.update_matches();
+    }
+
+    pub fn pop(&mut self) {
+        self.query.pop();
+        self.update_matches();
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.matches.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Matching items, best first
+    pub fn matches(&self) -> impl Iterator<Item = &PaletteItem> {
+        self.matches.iter().map(|&i| &self.items[i])
+    }
+
+    pub fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    pub fn selected_item(&self) -> Option<&PaletteItem> {
+        self.matches.get(self.selected).map(|&i| &self.items[i])
+    }
+
+    fn update_matches(&mut self) {
+        let words: Vec<&str> = self.query.split_whitespace().collect();
+        let mut scored: Vec<(i64, usize)> = self.items.iter()
+            .enumerate()
+            .filter_map(|(i, item)| {
+                // Every word has to match somewhere
+                let score = words.iter().map(|word| fuzzy_score(word, &item.search_text)).sum::<Option<i64>>()?;
+                Some((score, i))
+            })
+            .collect();
+        // Stable, so equal scores keep the list order
+        scored.sort_by_key(|(score, _)| -score);
+        self.matches = scored.into_iter().map(|(_, i)| i).collect();
+        self.selected = 0;
+    }
+}
+
+fn current_hint(current: bool) -> String {
+    if current { String::from("current") } else { String::new() }
+}
+
+/// Score `query` against `text` when its characters appear in order, ignoring case.
+/// Runs of consecutive characters and matches at the start of words score higher,
+/// gaps score lower.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let first = *query.first()?;
+
+    // Try each place the first character appears, as the leftmost isn't always the best
+    (0..text.len())
+        .filter(|&start| text[start] == first)
+        .filter_map(|start| score_from(&query, &text, start))
+        .max()
+}
+
+fn score_from(query: &[char], text: &[char], start: usize) -> Option<i64> {
+    let mut score = 0;
+    let mut position = start;
+    let mut previous: Option<usize> = None;
+
+    for &c in query {
+        let found = (position..text.len()).find(|&i| text[i] == c)?;
+        score += 1;
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+        match previous {
+            Some(previous) if found == previous + 1 => score += 5,
+            Some(previous) => score -= (found - previous - 1).min(5) as i64,
+            None => {}
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::NewsStory;
+    use crate::theme::Theme;
+
+    fn labels(palette: &Palette) -> Vec<&str> {
+        palette.matches().map(|item| item.label.as_str()).collect()
+    }
+
+    fn palette_for(query: &str) -> Palette {
+        let mut app = App::new(Theme::light());
+        app.update_stories(vec![NewsStory {
+            title: String::from("Storm Darragh brings heavy rain"),
+            description: String::new(),
+            link: String::from("https://www.bbc.co.uk/news/storm"),
+            pub_date: String::from("2025-01-01 12:00:00"),
+            category: String::from("News"),
+            image_url: None,
+        }]);
+        let mut palette = Palette::new(&app, KeyMode::Normal);
+        for c in query.chars() {
+            palette.push(c);
+        }
+        palette
+    }
+
+    #[test]
+    fn test_fuzzy_score_prefers_word_starts_and_runs() {
+        assert!(fuzzy_score("thm", "Cycle theme").is_some());
+        assert!(fuzzy_score("xyz", "Cycle theme").is_none());
+        assert!(fuzzy_score("the", "Cycle theme") > fuzzy_score("the", "Toggle this help menu"));
+        assert!(fuzzy_score("sn", "Sort: Newest First") > fuzzy_score("sn", "Search stories"));
+    }
+
+    #[test]
+    fn test_finds_actions_settings_feeds_and_stories() {
+        assert_eq!(labels(&palette_for("toggle theme"))[0], "Cycle theme");
+        assert_eq!(labels(&palette_for("sort newest"))[0], "Sort: Newest First");
+        assert_eq!(labels(&palette_for("feed tech"))[0], "Feed: Technology");
+        assert_eq!(labels(&palette_for("darragh"))[0], "Storm Darragh brings heavy rain");
+    }
+
+    #[test]
+    fn test_empty_query_lists_everything_in_order() {
+        let palette = palette_for("");
+        assert_eq!(palette.match_count(), palette.items.len());
+        assert!(matches!(palette.selected_item().unwrap().command, Command::Action(KeyMode::Normal, _)));
+    }
+
+    #[test]
+    fn test_selection_stays_in_matches() {
+        let mut palette = palette_for("feed");
+        palette.previous();
+        assert_eq!(palette.selected, 0);
+        for _ in 0..100 {
+            palette.next();
+        }
+        assert_eq!(palette.selected, palette.match_count() - 1);
+
+        palette.push('z');
+        palette.push('z');
+        assert_eq!(palette.selected, 0);
+        assert!(palette.selected_item().is_none());
+    }
+}
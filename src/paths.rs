@@ -0,0 +1,383 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cache::write_atomic;
+
+const APP_DIR: &str = "bbcli";
+const CONFIG_FILE: &str = "config.toml";
+const THEMES_DIR: &str = "themes";
+const LEGACY_DIR: &str = ".bbcli";
+
+// Files that lived directly in ~/.bbcli and now belong in the data directory
+const LEGACY_DATA_FILES: [&str; 3] = ["read.bin", "bookmarks.bin", "archive.db"];
+
+/// Config file: $BBCLI_CONFIG, else $XDG_CONFIG_HOME/bbcli/config.toml
+pub fn config_file() -> Result<PathBuf> {
+    if let Some(path) = env_path("BBCLI_CONFIG") {
+        return Ok(path);
+    }
+    xdg_dir("XDG_CONFIG_HOME", dirs::config_dir, ".config")
+        .map(|dir| dir.join(APP_DIR).join(CONFIG_FILE))
+        .context("Could not determine config path")
+}
+
+/// Theme files: themes/*.toml next to the config file
+pub fn themes_dir() -> Result<PathBuf> {
+    config_file()?
+        .parent()
+        .map(|dir| dir.join(THEMES_DIR))
+        .context("Could not determine themes directory")
+}
+
+/// Cache directory: $BBCLI_CACHE_DIR, else $XDG_CACHE_HOME/bbcli
+pub fn cache_dir() -> Result<PathBuf> {
+    if let Some(path) = env_path("BBCLI_CACHE_DIR") {
+        return Ok(path);
+    }
+    xdg_dir("XDG_CACHE_HOME", dirs::cache_dir, ".cache")
+        .map(|dir| dir.join(APP_DIR))
+        .context("Could not determine cache directory")
+}
+
+/// Data directory for persistent user data (read state, bookmarks, archive): $XDG_DATA_HOME/bbcli
+pub fn data_dir() -> Result<PathBuf> {
+    xdg_dir("XDG_DATA_HOME", dirs::data_dir, ".local/share")
+        .map(|dir| dir.join(APP_DIR))
+        .context("Could not determine data directory")
+}
+
+fn env_path(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+// Honour the XDG variable on every platform, then the platform default, then ~/<fallback>
+fn xdg_dir(var: &str, platform_dir: fn() -> Option<PathBuf>, fallback: &str) -> Option<PathBuf> {
+    env_path(var)
+        .filter(|path| path.is_absolute())
+        .or_else(platform_dir)
+        .or_else(|| dirs::home_dir().map(|home| home.join(fallback)))
+}
+
+/// Move files from the old layouts into the XDG locations.
+/// Returns a message for each thing that was moved or needs attention.
+pub fn migrate_legacy_layout() -> Vec<String> {
+    let (Some(home), Ok(config_file), Ok(cache_dir), Ok(data_dir)) =
+        (dirs::home_dir(), config_file(), cache_dir(), data_dir())
+    else {
+        return Vec::new();
+    };
+
+    let mut legacy_config_paths = vec![home.join(LEGACY_DIR)];
+    if let Some(config_home) = xdg_dir("XDG_CONFIG_HOME", dirs::config_dir, ".config") {
+        legacy_config_paths.push(config_home.join(APP_DIR));
+    }
+
+    migrate_layout(&home.join(LEGACY_DIR), &legacy_config_paths, &config_file, &cache_dir, &data_dir)
+}
+
+fn migrate_layout(
+    legacy_dir: &Path,
+    legacy_config_paths: &[PathBuf],
+    config_file: &Path,
+    cache_dir: &Path,
+    data_dir: &Path,
+) -> Vec<String> {
+    let mut messages = Vec::new();
+
+    // Old config locations were plain files: ~/.bbcli or ~/.config/bbcli
+    for legacy_config in legacy_config_paths {
+        if !legacy_config.is_file() {
+            continue;
+        }
+        if config_file.exists() {
+            messages.push(format!(
+                "ignoring old config file {} because {} exists; remove it to silence this warning",
+                legacy_config.display(),
+                config_file.display()
+            ));
+            continue;
+        }
+        match move_config(legacy_config, config_file) {
+            Ok(()) => messages.push(format!("moved config {} to {}", legacy_config.display(), config_file.display())),
+            Err(e) => messages.push(format!("could not move config {}: {:#}", legacy_config.display(), e)),
+        }
+    }
+
+    // Old data layout: ~/.bbcli/ directory holding cache/ and data files
+    if legacy_dir.is_dir() {
+        let mut moved = 0;
+        let mut failed = Vec::new();
+        let mut in_use = false;  // The cache or data directory is configured to be inside it
+
+        let legacy_cache = legacy_dir.join("cache");
+        if same_path(&legacy_cache, cache_dir) {
+            in_use = true;
+        } else if let Ok(entries) = fs::read_dir(&legacy_cache) {
+            for entry in entries.flatten() {
+                let name = entry.file_name();
+                // Lock files are recreated on demand
+                if name == ".lock" {
+                    let _ = fs::remove_file(entry.path());
+                    continue;
+                }
+                match move_file(&entry.path(), &cache_dir.join(&name)) {
+                    Ok(true) => moved += 1,
+                    Ok(false) => {}
+                    Err(_) => failed.push(entry.path()),
+                }
+            }
+            let _ = fs::remove_dir(&legacy_cache);
+        }
+
+        for name in LEGACY_DATA_FILES {
+            let path = legacy_dir.join(name);
+            let destination = data_dir.join(name);
+            if !path.exists() {
+                continue;
+            }
+            if same_path(&path, &destination) {
+                in_use = true;
+                continue;
+            }
+            // Read state, bookmarks and the archive can't be merged, so both copies are kept
+            if destination.exists() {
+                messages.push(format!(
+                    "ignoring old {} because {} exists; remove it to silence this warning",
+                    path.display(),
+                    destination.display()
+                ));
+                continue;
+            }
+            match move_file(&path, &destination) {
+                Ok(true) => moved += 1,
+                Ok(false) => {}
+                Err(_) => failed.push(path),
+            }
+        }
+
+        if moved > 0 {
+            messages.push(format!(
+                "moved {} files from {} to {} and {}",
+                moved,
+                legacy_dir.display(),
+                cache_dir.display(),
+                data_dir.display()
+            ));
+        }
+        for path in failed {
+            messages.push(format!("could not move {}; move or remove it manually", path.display()));
+        }
+
+        // Only removed once empty, so anything unexpected is left alone
+        if !in_use && fs::remove_dir(legacy_dir).is_err() {
+            messages.push(format!(
+                "{} is no longer used; remove it once you've checked its contents",
+                legacy_dir.display()
+            ));
+        }
+    }
+
+    messages
+}
+
+fn move_config(from: &Path, to: &Path) -> Result<()> {
+    let content = fs::read(from)?;
+
+    // ~/.config/bbcli may be both the old file and the new directory, so set the file aside
+    // under another name until the new one has been written
+    let in_the_way = to.starts_with(from);
+    let original = if in_the_way {
+        let aside = from.with_extension("old");
+        fs::rename(from, &aside)?;
+        aside
+    } else {
+        from.to_path_buf()
+    };
+
+    let written = (|| -> Result<()> {
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_atomic(to, &content)
+    })();
+
+    if let Err(e) = written {
+        if in_the_way {
+            // Only an empty directory is removed, so this never loses anything
+            let _ = fs::remove_dir(from);
+            if fs::rename(&original, from).is_err() {
+                return Err(e.context(format!("the old config is kept at {}", original.display())));
+            }
+        }
+        return Err(e);
+    }
+    fs::remove_file(&original)?;
+    Ok(())
+}
+
+// Whether two paths are the same file or directory, however they're written
+fn same_path(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+// Returns false if the destination already exists (newer data wins, old file is removed)
+fn move_file(from: &Path, to: &Path) -> Result<bool> {
+    // Never remove a file because it's already where it should be
+    if same_path(from, to) {
+        return Ok(false);
+    }
+    if to.exists() {
+        fs::remove_file(from)?;
+        return Ok(false);
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Rename fails across filesystems, fall back to copy
+    if fs::rename(from, to).is_err() {
+        fs::copy(from, to)?;
+        fs::remove_file(from)?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Layout {
+        root: PathBuf,
+        config_file: PathBuf,
+        cache_dir: PathBuf,
+        data_dir: PathBuf,
+    }
+
+    fn layout(name: &str) -> Layout {
+        let root = std::env::temp_dir().join(format!("bbcli-paths-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Layout {
+            config_file: root.join("config/bbcli/config.toml"),
+            cache_dir: root.join("cache/bbcli"),
+            data_dir: root.join("data/bbcli"),
+            root,
+        }
+    }
+
+    fn migrate(layout: &Layout, legacy_config_paths: &[PathBuf]) -> Vec<String> {
+        migrate_layout(
+            &layout.root.join(".bbcli"),
+            legacy_config_paths,
+            &layout.config_file,
+            &layout.cache_dir,
+            &layout.data_dir,
+        )
+    }
+
+    #[test]
+    fn test_migrates_legacy_config_file() {
+        let layout = layout("config");
+        let legacy = layout.root.join(".bbcli");
+        fs::write(&legacy, "theme = \"dark\"\n").unwrap();
+
+        let messages = migrate(&layout, std::slice::from_ref(&legacy));
+        assert_eq!(messages.len(), 1);
+        assert!(!legacy.exists());
+        assert_eq!(fs::read_to_string(&layout.config_file).unwrap(), "theme = \"dark\"\n");
+
+        let _ = fs::remove_dir_all(&layout.root);
+    }
+
+    #[test]
+    fn test_migrates_config_file_where_config_dir_goes() {
+        // ~/.config/bbcli was a file, it must become a directory
+        let layout = layout("xdgfile");
+        let legacy = layout.root.join("config/bbcli");
+        fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+        fs::write(&legacy, "theme = \"dark\"\n").unwrap();
+
+        migrate(&layout, std::slice::from_ref(&legacy));
+        assert_eq!(fs::read_to_string(&layout.config_file).unwrap(), "theme = \"dark\"\n");
+        assert!(!legacy.with_extension("old").exists());
+
+        let _ = fs::remove_dir_all(&layout.root);
+    }
+
+    #[test]
+    fn test_keeps_config_when_it_cannot_be_moved() {
+        // A file where the config directory should be stops the new config being written
+        let layout = layout("blocked");
+        let legacy = layout.root.join(".bbcli");
+        fs::write(&legacy, "theme = \"dark\"\n").unwrap();
+        fs::write(layout.root.join("config"), "").unwrap();
+
+        let messages = migrate(&layout, std::slice::from_ref(&legacy));
+        assert!(messages[0].starts_with("could not move config"), "{:?}", messages);
+        assert_eq!(fs::read_to_string(&legacy).unwrap(), "theme = \"dark\"\n");
+        assert!(!layout.config_file.exists());
+
+        let _ = fs::remove_dir_all(&layout.root);
+    }
+
+    #[test]
+    fn test_migrates_legacy_data_directory() {
+        let layout = layout("data");
+        let legacy = layout.root.join(".bbcli");
+        fs::create_dir_all(legacy.join("cache")).unwrap();
+        fs::write(legacy.join("cache/feed_abc.bin"), "feed").unwrap();
+        fs::write(legacy.join("cache/.lock"), "").unwrap();
+        fs::write(legacy.join("bookmarks.bin"), "bookmarks").unwrap();
+
+        let messages = migrate(&layout, std::slice::from_ref(&legacy));
+        assert_eq!(messages.len(), 1, "{:?}", messages);
+        assert!(!legacy.exists());
+        assert_eq!(fs::read_to_string(layout.cache_dir.join("feed_abc.bin")).unwrap(), "feed");
+        assert_eq!(fs::read_to_string(layout.data_dir.join("bookmarks.bin")).unwrap(), "bookmarks");
+
+        let _ = fs::remove_dir_all(&layout.root);
+    }
+
+    #[test]
+    fn test_leaves_cache_alone_when_it_is_the_legacy_directory() {
+        // BBCLI_CACHE_DIR pointing at ~/.bbcli/cache
+        let layout = layout("samedir");
+        let legacy = layout.root.join(".bbcli");
+        fs::create_dir_all(legacy.join("cache")).unwrap();
+        fs::write(legacy.join("cache/feed_abc.bin"), "feed").unwrap();
+
+        let messages = migrate_layout(&legacy, &[], &layout.config_file, &legacy.join("./cache"), &layout.data_dir);
+        assert!(messages.is_empty(), "{:?}", messages);
+        assert_eq!(fs::read_to_string(legacy.join("cache/feed_abc.bin")).unwrap(), "feed");
+        assert!(!move_file(&legacy.join("cache/feed_abc.bin"), &legacy.join("cache/../cache/feed_abc.bin")).unwrap());
+        assert!(legacy.join("cache/feed_abc.bin").exists());
+
+        let _ = fs::remove_dir_all(&layout.root);
+    }
+
+    #[test]
+    fn test_keeps_new_files_and_warns_about_leftovers() {
+        let layout = layout("conflict");
+        let legacy = layout.root.join(".bbcli");
+        fs::create_dir_all(&legacy).unwrap();
+        fs::write(legacy.join("read.bin"), "old").unwrap();
+        fs::write(legacy.join("notes.txt"), "unknown").unwrap();
+        fs::create_dir_all(&layout.data_dir).unwrap();
+        fs::write(layout.data_dir.join("read.bin"), "new").unwrap();
+
+        let messages = migrate(&layout, std::slice::from_ref(&legacy));
+        assert_eq!(fs::read_to_string(layout.data_dir.join("read.bin")).unwrap(), "new");
+        // The old copy may hold data the new one doesn't, so it's kept
+        assert_eq!(fs::read_to_string(legacy.join("read.bin")).unwrap(), "old");
+        assert!(messages.iter().any(|m| m.starts_with("ignoring old") && m.contains("read.bin")), "{:?}", messages);
+        assert!(legacy.join("notes.txt").exists());
+        assert!(messages.iter().any(|m| m.contains("no longer used")));
+
+        let _ = fs::remove_dir_all(&layout.root);
+    }
+}
@@ -0,0 +1,161 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::app::NewsStory;
+use crate::cache::write_atomic;
+use crate::paths::data_dir;
+
+const READ_STATE_FILE: &str = "read.bin";
+const READ_EXPIRY_SECS: u64 = 60 * 60 * 24 * 30; // Forget read state after 30 days
+
+#[derive(Serialize, Deserialize, Default)]
+struct ReadStateFile {
+    entries: HashMap<String, u64>, // Story link -> time marked read
+}
+
+/// Tracks which stories have been read, persisted across sessions
+#[derive(Default)]
+pub struct ReadState {
+    path: Option<PathBuf>,
+    entries: HashMap<String, u64>,
+}
+
+impl ReadState {
+    /// Load read state from the data directory (empty if missing or unreadable)
+    pub fn load() -> Self {
+        match data_dir() {
+            Ok(dir) => Self::load_from(dir.join(READ_STATE_FILE)),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn load_from(path: PathBuf) -> Self {
+        let mut entries = fs::read(&path)
+            .ok()
+            .and_then(|data| bincode::deserialize::<ReadStateFile>(&data).ok())
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        // Drop entries old enough that the story can no longer be in any feed
+        let now = Self::current_timestamp();
+        entries.retain(|_, marked_at| now.saturating_sub(*marked_at) < READ_EXPIRY_SECS);
+
+        Self { path: Some(path), entries }
+    }
+
+    fn current_timestamp() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    pub fn is_read(&self, link: &str) -> bool {
+        self.entries.contains_key(link)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn mark_read(&mut self, link: &str) {
+        if !self.is_read(link) {
+            self.entries.insert(link.to_string(), Self::current_timestamp());
+            let _ = self.save();
+        }
+    }
+
+    /// Toggle read state, returns true if the story is now read
+    pub fn toggle(&mut self, link: &str) -> bool {
+        let now_read = if self.entries.remove(link).is_some() {
+            false
+        } else {
+            self.entries.insert(link.to_string(), Self::current_timestamp());
+            true
+        };
+        let _ = self.save();
+        now_read
+    }
+
+    pub fn mark_all_read(&mut self, stories: &[NewsStory]) {
+        let now = Self::current_timestamp();
+        for story in stories {
+            self.entries.entry(story.link.clone()).or_insert(now);
+        }
+        let _ = self.save();
+    }
+
+    pub fn unread_count(&self, stories: &[NewsStory]) -> usize {
+        stories.iter().filter(|s| !self.is_read(&s.link)).count()
+    }
+
+    fn save(&self) -> Result<()> {
+        // In-memory only (e.g. no home directory)
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = ReadStateFile { entries: self.entries.clone() };
+        write_atomic(path, &bincode::serialize(&file)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bbcli-read-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join(READ_STATE_FILE)
+    }
+
+    #[test]
+    fn test_read_state_survives_reload() {
+        let path = temp_file("roundtrip");
+        let mut state = ReadState::load_from(path.clone());
+        state.mark_read("https://www.bbc.co.uk/news/1");
+        state.mark_read("https://www.bbc.co.uk/news/2");
+        assert!(!state.toggle("https://www.bbc.co.uk/news/2"));
+
+        let state = ReadState::load_from(path.clone());
+        assert!(state.is_read("https://www.bbc.co.uk/news/1"));
+        assert!(!state.is_read("https://www.bbc.co.uk/news/2"));
+        assert_eq!(state.len(), 1);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_read_state_expires_after_30_days() {
+        let path = temp_file("expiry");
+        let now = ReadState::current_timestamp();
+        let file = ReadStateFile {
+            entries: HashMap::from([
+                (String::from("old"), now - READ_EXPIRY_SECS - 1),
+                (String::from("recent"), now - 60 * 60 * 24 * 29),
+            ]),
+        };
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, bincode::serialize(&file).unwrap()).unwrap();
+
+        let state = ReadState::load_from(path.clone());
+        assert!(!state.is_read("old"));
+        assert!(state.is_read("recent"));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
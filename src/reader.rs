@@ -0,0 +1,257 @@
+use std::ops::Range;
+
+use crate::config::ReaderConfig;
+
+/// A line of the article as shown, with where it starts in the article text
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArticleLine {
+    pub text: String,
+    pub start: usize, // Char offset in the article, which survives re-wrapping at another width
+}
+
+// Average adult silent reading speed, for the reading time estimate
+const WORDS_PER_MINUTE: usize = 230;
+
+/// Lay the article out at a width: paragraphs wrapped at spaces where possible, blank lines
+/// between paragraphs evened out, and optionally justified and with hanging quotes and lists.
+pub fn wrap(text: &str, width: usize, options: &ReaderConfig) -> Vec<ArticleLine> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    let mut blank_run = 0;
+
+    for paragraph in text.split('\n') {
+        let chars: Vec<char> = paragraph.trim_end_matches('\r').chars().collect();
+        if chars.iter().all(|c| c.is_whitespace()) {
+            if blank_run < options.paragraph_spacing {
+                lines.push(ArticleLine { text: String::new(), start: offset });
+            }
+            blank_run += 1;
+        } else {
+            // Top up a short gap between paragraphs
+            if blank_run > 0 {
+                for _ in blank_run..options.paragraph_spacing {
+                    lines.push(ArticleLine { text: String::new(), start: offset });
+                }
+            }
+            blank_run = 0;
+            wrap_paragraph(&chars, offset, width, options, &mut lines);
+        }
+        offset += paragraph.chars().count() + 1;
+    }
+    lines
+}
+
+fn wrap_paragraph(chars: &[char], offset: usize, width: usize, options: &ReaderConfig, lines: &mut Vec<ArticleLine>) {
+    // A marker that doesn't leave room for text wraps like any other paragraph
+    let marker = hanging_marker(chars).filter(|&marker| marker < width / 2).unwrap_or(0);
+    let first_prefix: String = chars[..marker].iter().collect();
+    let prefix = if options.hanging_indent { " ".repeat(marker) } else { first_prefix.clone() };
+    let body_width = width - marker;
+
+    let mut start = marker;
+    while start < chars.len() {
+        let end = (start + body_width).min(chars.len());
+        let mut next = end;
+        if end < chars.len() {
+            // Break at the last space that fits, or mid-word if there isn't one
+            if let Some(space) = chars[start..=end].iter().rposition(|c| *c == ' ').filter(|&space| space > 0) {
+                next = start + space;
+            }
+        }
+        let mut body: String = chars[start..next].iter().collect::<String>().trim_end().to_string();
+        if options.justify && next < chars.len() {
+            body = justify(&body, body_width);
+        }
+        let line_prefix = if start == marker { &first_prefix } else { &prefix };
+        lines.push(ArticleLine { text: format!("{}{}", line_prefix, body), start: offset + start });
+
+        start = next;
+        while chars.get(start) == Some(&' ') {
+            start += 1;
+        }
+    }
+}
+
+// Length of a leading quote marker ("> ", "> > ") or list bullet ("* ", "- ", "12. ")
+fn hanging_marker(chars: &[char]) -> Option<usize> {
+    let mut quote = 0;
+    while chars.get(quote) == Some(&'>') && chars.get(quote + 1) == Some(&' ') {
+        quote += 2;
+    }
+    if quote > 0 {
+        return Some(quote);
+    }
+
+    match chars {
+        ['*' | '-' | '•', ' ', ..] => Some(2),
+        _ => {
+            let digits = chars.iter().take_while(|c| c.is_ascii_digit()).count();
+            (digits > 0 && chars.get(digits) == Some(&'.') && chars.get(digits + 1) == Some(&' ')).then_some(digits + 2)
+        }
+    }
+}
+
+// Widen the gaps between words so the line fills the width. Lines well short of it are
+// left alone rather than stretched into a few far-apart words.
+fn justify(line: &str, width: usize) -> String {
+    let words: Vec<&str> = line.split(' ').filter(|word| !word.is_empty()).collect();
+    let word_chars: usize = words.iter().map(|word| word.chars().count()).sum();
+    let gaps = words.len().saturating_sub(1);
+    let length = word_chars + gaps;
+    if gaps == 0 || length >= width || width - length > width / 3 {
+        return line.to_string();
+    }
+
+    let spaces = width - word_chars;
+    let mut justified = String::new();
+    for (i, word) in words.iter().enumerate() {
+        justified.push_str(word);
+        if i < gaps {
+            // Leftmost gaps take the remainder
+            let gap = spaces / gaps + usize::from(i < spaces % gaps);
+            justified.push_str(&" ".repeat(gap));
+        }
+    }
+    justified
+}
+
+/// Word count and estimated minutes to read
+pub fn reading_time(text: &str) -> (usize, usize) {
+    let words = text.split_whitespace().count();
+    (words, words.div_ceil(WORDS_PER_MINUTE).max(1))
+}
+
+/// Char ranges of case-insensitive matches of `query` in `text`. A space in the query matches
+/// any run of spaces, so justified lines still match.
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let fold = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let text: Vec<char> = text.chars().map(fold).collect();
+    let query: Vec<char> = query.chars().map(fold).collect();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        match match_end(&text, start, &query) {
+            Some(end) => {
+                matches.push(start..end);
+                start = end;
+            }
+            None => start += 1,
+        }
+    }
+    matches
+}
+
+// End of a match of `query` starting at `start`, if there is one
+fn match_end(text: &[char], start: usize, query: &[char]) -> Option<usize> {
+    let mut i = start;
+    for &c in query {
+        if text.get(i) != Some(&c) {
+            return None;
+        }
+        i += 1;
+        if c == ' ' {
+            while text.get(i) == Some(&' ') {
+                i += 1;
+            }
+        }
+    }
+    Some(i)
+}
+
+/// Lines with at least one match, in order
+pub fn match_lines(lines: &[ArticleLine], query: &str) -> Vec<usize> {
+    lines.iter()
+        .enumerate()
+        .filter(|(_, line)| !find_matches(&line.text, query).is_empty())
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// The line holding a char offset
+pub fn line_at(lines: &[ArticleLine], offset: usize) -> usize {
+    lines.partition_point(|line| line.start <= offset).saturating_sub(1)
+}
+
+/// Furthest scroll that still fills the view
+pub fn max_scroll(line_count: usize, height: usize) -> usize {
+    line_count.saturating_sub(height)
+}
+
+/// How far through the article the view is, as a percentage
+pub fn progress(scroll: usize, line_count: usize, height: usize) -> usize {
+    match max_scroll(line_count, height) {
+        0 => 100,
+        max => scroll.min(max) * 100 / max,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[ArticleLine]) -> Vec<&str> {
+        lines.iter().map(|line| line.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_wraps_at_spaces_and_keeps_offsets() {
+        let text = "The quick brown fox\n\njumps over";
+        let lines = wrap(text, 10, &ReaderConfig::default());
+        assert_eq!(texts(&lines), vec!["The quick", "brown fox", "", "jumps over"]);
+        assert_eq!(lines[1].start, 10);
+        assert_eq!(lines[3].start, 21);
+        assert_eq!(text.chars().skip(lines[3].start).take(5).collect::<String>(), "jumps");
+
+        // Words longer than the width are split
+        assert_eq!(texts(&wrap("abcdefgh", 3, &ReaderConfig::default())), vec!["abc", "def", "gh"]);
+    }
+
+    #[test]
+    fn test_reading_options() {
+        let text = "First paragraph here\n\n\n\n> A quoted line that wraps\n* A list item that wraps";
+        let options = ReaderConfig { justify: true, paragraph_spacing: 2, ..ReaderConfig::default() };
+        // A lone word and the last line of each paragraph stay as they are
+        assert_eq!(texts(&wrap(text, 14, &options)), vec![
+            "First",
+            "paragraph here",
+            "",
+            "",
+            "> A     quoted",
+            "  line    that",
+            "  wraps",
+            "* A  list item",
+            "  that wraps",
+        ]);
+
+        // Without hanging indents, quotes repeat their marker
+        let options = ReaderConfig { hanging_indent: false, paragraph_spacing: 0, ..ReaderConfig::default() };
+        assert_eq!(texts(&wrap(text, 14, &options))[1..4], ["paragraph here", "> A quoted", "> line that"]);
+
+        assert_eq!(reading_time(&"word ".repeat(500)), (500, 3));
+    }
+
+    #[test]
+    fn test_finds_matches_ignoring_case() {
+        assert_eq!(find_matches("Über the über", "ÜBER"), vec![0..4, 9..13]);
+        assert!(find_matches("news", "").is_empty());
+        assert_eq!(find_matches("spread   out", "spread out"), vec![0..12]);
+
+        let lines = wrap("one fish\ntwo fish\nred", 20, &ReaderConfig::default());
+        assert_eq!(match_lines(&lines, "fish"), vec![0, 1]);
+        assert_eq!(line_at(&lines, 12), 1);
+    }
+
+    #[test]
+    fn test_progress_through_article() {
+        assert_eq!(max_scroll(100, 20), 80);
+        assert_eq!(progress(0, 100, 20), 0);
+        assert_eq!(progress(40, 100, 20), 50);
+        assert_eq!(progress(500, 100, 20), 100);
+        assert_eq!(progress(0, 10, 20), 100);
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cache::write_atomic;
+use crate::paths::data_dir;
+
+const POSITIONS_FILE: &str = "positions.bin";
+const POSITION_EXPIRY_SECS: u64 = 60 * 60 * 24 * 30; // Forget positions after 30 days
+
+#[derive(Serialize, Deserialize, Default)]
+struct PositionsFile {
+    entries: HashMap<String, (usize, u64)>, // Story link -> (char offset, time saved)
+}
+
+/// Where reading stopped in each article, persisted across sessions. Positions are char
+/// offsets into the article text, so they still fit when the window is a different width.
+#[derive(Default)]
+pub struct ReadingPositions {
+    path: Option<PathBuf>,
+    entries: HashMap<String, (usize, u64)>,
+}
+
+impl ReadingPositions {
+    /// Load positions from the data directory (empty if missing or unreadable)
+    pub fn load() -> Self {
+        match data_dir() {
+            Ok(dir) => Self::load_from(dir.join(POSITIONS_FILE)),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn load_from(path: PathBuf) -> Self {
+        let mut entries = fs::read(&path)
+            .ok()
+            .and_then(|data| bincode::deserialize::<PositionsFile>(&data).ok())
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        let now = current_timestamp();
+        entries.retain(|_, (_, saved_at)| now.saturating_sub(*saved_at) < POSITION_EXPIRY_SECS);
+
+        Self { path: Some(path), entries }
+    }
+
+    pub fn get(&self, link: &str) -> Option<usize> {
+        self.entries.get(link).map(|(offset, _)| *offset)
+    }
+
+    /// Remember a position. None forgets it, so the article opens at the top next time.
+    pub fn set(&mut self, link: &str, offset: Option<usize>) {
+        let changed = match offset {
+            Some(offset) => self.entries.insert(link.to_string(), (offset, current_timestamp())).is_none_or(|(old, _)| old != offset),
+            None => self.entries.remove(link).is_some(),
+        };
+        if changed {
+            let _ = self.save();
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        // In-memory only (e.g. no home directory)
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = PositionsFile { entries: self.entries.clone() };
+        write_atomic(path, &bincode::serialize(&file)?)?;
+        Ok(())
+    }
+}
+
+fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("bbcli-positions-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join(POSITIONS_FILE)
+    }
+
+    #[test]
+    fn test_positions_survive_reload() {
+        let path = temp_file("roundtrip");
+        let mut positions = ReadingPositions::load_from(path.clone());
+        positions.set("https://www.bbc.co.uk/news/1", Some(420));
+        positions.set("https://www.bbc.co.uk/news/2", Some(80));
+        positions.set("https://www.bbc.co.uk/news/2", None);
+
+        let positions = ReadingPositions::load_from(path.clone());
+        assert_eq!(positions.get("https://www.bbc.co.uk/news/1"), Some(420));
+        assert_eq!(positions.get("https://www.bbc.co.uk/news/2"), None);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_old_positions_expire() {
+        let path = temp_file("expiry");
+        let now = current_timestamp();
+        let file = PositionsFile {
+            entries: HashMap::from([
+                (String::from("old"), (10, now - POSITION_EXPIRY_SECS - 1)),
+                (String::from("recent"), (20, now - 60)),
+            ]),
+        };
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, bincode::serialize(&file).unwrap()).unwrap();
+
+        let positions = ReadingPositions::load_from(path.clone());
+        assert_eq!(positions.get("old"), None);
+        assert_eq!(positions.get("recent"), Some(20));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_unreadable_file_starts_empty() {
+        let path = temp_file("corrupt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"not bincode").unwrap();
+
+        let mut positions = ReadingPositions::load_from(path.clone());
+        assert_eq!(positions.get("https://www.bbc.co.uk/news/1"), None);
+        // And is replaced on the next save
+        positions.set("https://www.bbc.co.uk/news/1", Some(5));
+        assert_eq!(ReadingPositions::load_from(path.clone()).get("https://www.bbc.co.uk/news/1"), Some(5));
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
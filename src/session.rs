@@ -0,0 +1,251 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::app::{App, ImageProtocol, LayoutMode, SortOrder};
+use crate::cache::write_atomic;
+use crate::config::DisplayConfig;
+use crate::feeds::get_menu_feeds;
+use crate::paths::data_dir;
+use crate::tabs::Tab;
+use crate::theme::ThemeName;
+
+const SESSION_FILE: &str = "session.toml";
+
+/// Runtime toggles and position from the last run.
+/// Stored as TOML with defaults for every field, so older session files keep loading as fields are added.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub theme: ThemeName,
+    pub sort: SortOrder,
+    pub humanize_dates: bool,
+    pub image_protocol: ImageProtocol,
+    pub preview: bool,
+    pub layout: Option<LayoutMode>,    // Missing from older files, which keep the configured layout
+    pub feed_url: Option<String>,      // Current feed (pseudo-feeds other than Saved aren't restored)
+    pub selected_link: Option<String>, // Selected story, by link since positions change between fetches
+    pub article_open: bool,            // Full article view was open
+    pub article_scroll: usize,         // Scroll offset in the article view
+    pub tabs: Vec<String>,             // Feeds open in tabs, in order
+}
+
+// Missing fields get the same values as a new App
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            theme: ThemeName::default(),
+            sort: SortOrder::default(),
+            humanize_dates: true,
+            image_protocol: ImageProtocol::default(),
+            preview: false,
+            layout: None,
+            feed_url: None,
+            selected_link: None,
+            article_open: false,
+            article_scroll: 0,
+            tabs: Vec::new(),
+        }
+    }
+}
+
+impl Session {
+    pub fn path() -> Result<PathBuf> {
+        Ok(data_dir()?.join(SESSION_FILE))
+    }
+
+    /// Load the last session, if there is one
+    pub fn load() -> Option<Self> {
+        let content = fs::read_to_string(Self::path().ok()?).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        write_atomic(&path, toml::to_string(self)?.as_bytes())
+    }
+
+    /// Record the current state of the app
+    pub fn capture(app: &App) -> Self {
+        Self {
+            theme: app.theme.config_name(),
+            sort: app.sort_order.clone(),
+            humanize_dates: app.humanize_dates,
+            image_protocol: app.image_protocol.clone(),
+            preview: app.show_preview,
+            layout: Some(app.layout.mode),
+            feed_url: Some(app.current_feed.url.clone()).filter(|_| !app.current_feed.is_archive()),
+            selected_link: app.stories.get(app.selected).map(|story| story.link.clone()),
+            article_open: app.show_full_article,
+            article_scroll: app.article_scroll_offset,
+            tabs: app.tabs.iter()
+                .enumerate()
+                .map(|(i, tab)| if i == app.active_tab { &app.current_feed } else { &tab.feed })
+                .filter(|feed| !feed.is_archive())
+                .map(|feed| feed.url.clone())
+                .collect(),
+        }
+    }
+
+    /// Restore the feed and tabs. Call before the first fetch. The theme and toggles are
+    /// left as the config sets them, so editing the config always takes effect;
+    /// `bbcli config save-defaults` copies them into the config instead.
+    pub fn restore(&self, app: &mut App) {
+        if let Some(feed) = self.feed_url.as_ref()
+            .and_then(|url| get_menu_feeds().into_iter().find(|feed| &feed.url == url))
+        {
+            app.select_feed(feed);
+        }
+
+        // The other tabs load in the background
+        let mut tabs: Vec<Tab> = self.tabs.iter()
+            .filter_map(|url| get_menu_feeds().into_iter().find(|feed| &feed.url == url))
+            .map(Tab::new)
+            .collect();
+        app.active_tab = match tabs.iter().position(|tab| tab.feed.url == app.current_feed.url) {
+            Some(index) => index,
+            None => {
+                tabs.insert(0, Tab::new(app.current_feed.clone()));
+                0
+            }
+        };
+        app.tabs = tabs;
+    }
+
+    /// Restore the selected story and article view. Call once stories are loaded.
+    pub fn restore_position(&self, app: &mut App) {
+        let Some(index) = self.selected_link.as_ref()
+            .and_then(|link| app.stories.iter().position(|story| &story.link == link))
+        else {
+            return;
+        };
+
+        app.selected = index;
+        if self.article_open {
+            app.fetch_and_show_article();
+            if app.show_full_article {
+                app.scroll_article_to(self.article_scroll);
+            }
+        }
+    }
+
+    /// The display toggles as config defaults
+    pub fn display_config(&self) -> DisplayConfig {
+        DisplayConfig {
+            sort: self.sort.clone(),
+            humanize_dates: self.humanize_dates,
+            image_protocol: self.image_protocol.clone(),
+            preview: self.preview,
+        }
+    }
+
+    /// Write the toggles into config TOML as defaults, keeping comments and other settings
+    pub fn write_to_config(&self, content: &str) -> Result<String> {
+        let mut doc: toml_edit::DocumentMut = content.parse().context("Failed to parse config file")?;
+
+        doc["theme"] = toml_edit::value(toml_value(&self.theme)?);
+
+        let display = toml_edit::ser::to_document(&self.display_config())?;
+        for (key, value) in display.iter() {
+            set_table_value(&mut doc, "display", key, value.as_value().context("Unexpected display value")?.clone());
+        }
+        if let Some(layout) = self.layout {
+            set_table_value(&mut doc, "layout", "mode", toml_value(&layout)?.into());
+        }
+
+        Ok(doc.to_string())
+    }
+}
+
+// Assign the value rather than the item, so trailing comments on existing keys survive
+fn set_table_value(doc: &mut toml_edit::DocumentMut, table: &str, key: &str, value: toml_edit::Value) {
+    if !doc.contains_table(table) {
+        doc[table] = toml_edit::table();
+    }
+    match doc[table].get_mut(key).and_then(|item| item.as_value_mut()) {
+        Some(existing) => {
+            let decor = existing.decor().clone();
+            *existing = value;
+            *existing.decor_mut() = decor;
+        }
+        None => doc[table][key] = toml_edit::value(value),
+    }
+}
+
+// Serialize a unit enum to its config string
+fn toml_value<T: Serialize>(value: &T) -> Result<String> {
+    let value = toml::Value::try_from(value)?;
+    value.as_str().map(String::from).context("Expected a string value")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_config;
+    use crate::theme::Theme;
+
+    fn session() -> Session {
+        Session {
+            theme: ThemeName::new("dark"),
+            sort: SortOrder::DateNewest,
+            humanize_dates: false,
+            image_protocol: ImageProtocol::Kitty,
+            preview: true,
+            layout: Some(LayoutMode::Panes),
+            feed_url: Some(String::from("https://feeds.bbci.co.uk/news/world/rss.xml")),
+            tabs: vec![
+                String::from("https://feeds.bbci.co.uk/news/technology/rss.xml"),
+                String::from("https://feeds.bbci.co.uk/news/world/rss.xml"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_restore_keeps_configured_toggles() {
+        let mut app = App::new(Theme::light());
+        session().restore(&mut app);
+
+        // Feed and tabs come back, the theme and toggles stay as configured
+        assert_eq!(app.current_feed.url, "https://feeds.bbci.co.uk/news/world/rss.xml");
+        assert_eq!(app.active_tab, 1);
+        assert_eq!(app.theme.name, "light");
+        assert_eq!(app.sort_order, SortOrder::Default);
+        assert!(app.humanize_dates);
+
+        let captured = Session::capture(&app);
+        assert_eq!(captured.tabs, session().tabs);
+        assert_eq!(captured.layout, Some(LayoutMode::Compact));
+    }
+
+    #[test]
+    fn test_older_session_files_still_load() {
+        let loaded: Session = toml::from_str("theme = \"dark\"\n").unwrap();
+        assert_eq!(loaded.theme, ThemeName::new("dark"));
+        assert_eq!(loaded.sort, SortOrder::Default);
+        // Missing toggles match a fresh start, not the type's zero value
+        assert!(loaded.humanize_dates);
+        assert_eq!(loaded.humanize_dates, App::new(Theme::light()).humanize_dates);
+    }
+
+    #[test]
+    fn test_write_to_config_keeps_comments() {
+        let content = "# My config\ntheme = \"light\"\n\n[display]\nsort = \"oldest\"  # keep this\n\n[keybindings]\nquit = 'x'\n";
+        let written = session().write_to_config(content).unwrap();
+
+        assert!(written.contains("# My config"));
+        assert!(written.contains("sort = \"newest\"  # keep this"));
+        assert!(written.contains("quit = 'x'"));
+
+        let (config, warnings) = parse_config(&written).unwrap();
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(config.theme, ThemeName::new("dark"));
+        assert_eq!(config.display, session().display_config());
+        assert_eq!(config.layout.mode, LayoutMode::Panes);
+        assert_eq!(config.keybindings.normal["quit"].0[0].to_string(), "x");
+    }
+}
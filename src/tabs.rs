@@ -0,0 +1,92 @@
+use std::time::Instant;
+
+use crate::app::{App, NewsStory, Pane, AUTO_REFRESH_INTERVAL};
+use crate::feeds::Feed;
+
+/// A feed open in a tab. The current tab's state lives in `App`; the others keep a copy
+/// of it here until they're switched back to.
+#[derive(Debug, Clone)]
+pub struct Tab {
+    pub feed: Feed,
+    pub stories: Vec<NewsStory>,
+    pub loaded: bool,                       // Stories have been fetched at least once
+    selected_link: Option<String>,          // Selected story, by link since a refresh can reorder stories
+    selected: usize,                        // Fallback when the selected story is gone
+    show_full_article: bool,
+    article_scroll_offset: usize,
+    search_query: String,
+    pub is_offline: bool,
+    pub offline_since: Option<chrono::DateTime<chrono::Local>>,
+    pub last_refresh_time: Option<Instant>, // Last fetch, or attempt at one
+}
+
+impl Tab {
+    /// A tab that hasn't been loaded yet
+    pub fn new(feed: Feed) -> Self {
+        Self {
+            feed,
+            stories: Vec::new(),
+            loaded: false,
+            selected_link: None,
+            selected: 0,
+            show_full_article: false,
+            article_scroll_offset: 0,
+            search_query: String::new(),
+            is_offline: false,
+            offline_since: None,
+            last_refresh_time: None,
+        }
+    }
+
+    /// Copy the current feed's state out of the app
+    pub fn capture(app: &App) -> Self {
+        Self {
+            feed: app.current_feed.clone(),
+            stories: app.stories.clone(),
+            loaded: !app.is_loading,
+            selected_link: app.stories.get(app.selected).map(|story| story.link.clone()),
+            selected: app.selected,
+            show_full_article: app.show_full_article,
+            article_scroll_offset: app.article_scroll_offset,
+            search_query: app.search_query.clone(),
+            is_offline: app.is_offline,
+            offline_since: app.offline_since,
+            last_refresh_time: Some(app.last_refresh_time),
+        }
+    }
+
+    /// Put this tab's state back into the app
+    pub fn restore(self, app: &mut App) {
+        app.current_feed = self.feed;
+        app.search_query = self.search_query;
+        app.is_offline = self.is_offline;
+        app.offline_since = self.offline_since;
+        app.last_refresh_time = self.last_refresh_time.unwrap_or_else(Instant::now);
+        app.clear_error();
+
+        app.update_stories(self.stories);
+        app.is_loading = !self.loaded;
+        app.selected = self.selected_link
+            .and_then(|link| app.stories.iter().position(|story| story.link == link))
+            .unwrap_or(self.selected.min(app.stories.len().saturating_sub(1)));
+
+        app.show_full_article = self.show_full_article;
+        app.article_scroll_offset = self.article_scroll_offset;
+        app.focus = if app.show_full_article { Pane::Reader } else { Pane::Stories };
+    }
+
+    /// Store freshly fetched stories
+    pub fn update_stories(&mut self, stories: Vec<NewsStory>) {
+        self.stories = stories;
+        self.loaded = true;
+        self.last_refresh_time = Some(Instant::now());
+    }
+
+    /// Whether a background refresh is due. Saved stories come from bookmarks and archive
+    /// results from a search, so only real feeds are fetched.
+    pub fn needs_refresh(&self) -> bool {
+        !self.feed.is_saved()
+            && !self.feed.is_archive()
+            && self.last_refresh_time.is_none_or(|time| time.elapsed() >= AUTO_REFRESH_INTERVAL)
+    }
+}
@@ -0,0 +1,77 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: &str = "...";
+
+/// Columns the text takes up in the terminal. Wide characters like CJK and most emoji take
+/// two, combining marks none.
+pub fn width(text: &str) -> usize {
+    text.width()
+}
+
+/// Shorten text to at most `max` columns, ending in "..." when anything was cut. Cuts fall
+/// between graphemes, so accents and emoji sequences are never split.
+pub fn truncate(text: &str, max: usize) -> String {
+    if width(text) <= max {
+        return text.to_string();
+    }
+    if max <= ELLIPSIS.len() {
+        return take_width(text, max).to_string();
+    }
+    format!("{}{}", take_width(text, max - ELLIPSIS.len()).trim_end(), ELLIPSIS)
+}
+
+/// Truncate or pad with spaces to exactly `columns`, for rows with a full-width background
+pub fn fit(text: &str, columns: usize) -> String {
+    let mut fitted = truncate(text, columns);
+    // A wide character that didn't fit leaves a column short
+    let used = width(&fitted);
+    fitted.push_str(&" ".repeat(columns.saturating_sub(used)));
+    fitted
+}
+
+// The longest run of whole graphemes from the start that fits in `max` columns
+fn take_width(text: &str, max: usize) -> &str {
+    let mut used = 0;
+    for (index, grapheme) in text.grapheme_indices(true) {
+        used += grapheme.width();
+        if used > max {
+            return &text[..index];
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measures_display_columns() {
+        assert_eq!(width("BBC News"), 8);
+        assert_eq!(width("“Café” in São Paulo"), 19);
+        assert_eq!(width("日本の地震"), 10);
+        assert_eq!(width("e\u{301}"), 1);
+        assert_eq!(width("★ "), 2);
+    }
+
+    #[test]
+    fn test_truncates_between_graphemes() {
+        assert_eq!(truncate("Short", 10), "Short");
+        assert_eq!(truncate("Ukraine: latest news", 12), "Ukraine:...");
+        // A wide character that would straddle the edge is left out
+        assert_eq!(truncate("中国经济增长放缓", 8), "中国...");
+        assert_eq!(truncate("Pokémon e\u{301}e\u{301}e\u{301}", 10), "Pokémon...");
+        assert_eq!(truncate("👩‍👩‍👧 family", 5), "👩‍👩‍👧...");
+        assert_eq!(truncate("中国经济", 3), "中");
+        assert_eq!(truncate("anything", 0), "");
+    }
+
+    #[test]
+    fn test_fits_to_exact_width() {
+        assert_eq!(fit("Hi", 5), "Hi   ");
+        assert_eq!(fit("中国经济增长", 7), "中国...");
+        assert_eq!(width(&fit("中国经济增长", 7)), 7);
+        assert_eq!(fit("中国经济", 3), "中 ");
+    }
+}
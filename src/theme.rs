@@ -1,22 +1,42 @@
-use ratatui::style::Color;
+use anyhow::{Context, Result};
+use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(rename_all = "lowercase")]
-pub enum ThemeName {
-    Light,
-    Dark,
+use crate::color_depth::ColorDepth;
+
+/// Name of a theme in config: a built-in like "dark", or one defined by the user
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct ThemeName(pub String);
+
+impl ThemeName {
+    pub fn new(name: &str) -> Self {
+        Self(name.to_string())
+    }
 }
 
 impl Default for ThemeName {
     fn default() -> Self {
-        ThemeName::Light
+        Self::new("light")
+    }
+}
+
+impl fmt::Display for ThemeName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
     }
 }
 
-#[derive(Debug, Clone)]
+/// Themes that ship with bbcli, in the order T cycles through them
+pub const BUILT_IN_THEMES: [&str; 5] = ["light", "dark", "solarized-light", "solarized-dark", "high-contrast"];
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Theme {
-    pub name: String,
+    pub name: String,           // Config name
     pub bg_primary: Color,      // Main background
     pub bg_secondary: Color,    // Alternate background (for list items)
     pub bg_accent: Color,       // Header/footer background
@@ -24,39 +44,423 @@ pub struct Theme {
     pub fg_secondary: Color,    // Secondary/muted text
     pub accent: Color,          // BBC red / selection color
     pub accent_fg: Color,       // Text color on accent background
+    pub selection_bg: Color,    // Selected story and menu item
+    pub selection_fg: Color,    // Text of the selected item
+    pub error: Color,           // Error messages
+    pub header_bg: Color,       // Header and tab bar background
+    pub header_fg: Color,       // Header text
+    pub ticker: Color,          // Ticker headline in the footer
+    pub link: Color,            // Story links
+    pub read: Color,            // Stories already read
+    pub monochrome: bool,       // No colours at all, highlights use reverse video
 }
 
 impl Theme {
     pub fn light() -> Self {
-        Self {
-            name: "Light".to_string(),
-            bg_primary: Color::Rgb(255, 254, 252),  // Off-white
-            bg_secondary: Color::Rgb(255, 254, 252), // Same as primary
-            bg_accent: Color::Rgb(230, 230, 227),    // Light gray for footer
-            fg_primary: Color::Rgb(0, 0, 0),         // Black text
-            fg_secondary: Color::Rgb(148, 148, 148), // Gray text
-            accent: Color::Rgb(234, 68, 57),         // BBC red
-            accent_fg: Color::Rgb(255, 255, 255),    // White on red
-        }
+        Self::built_in("light").expect("light is built in")
     }
 
     pub fn dark() -> Self {
+        Self::built_in("dark").expect("dark is built in")
+    }
+
+    /// A built-in theme by config name
+    pub fn built_in(name: &str) -> Option<Self> {
+        let spec = match name {
+            "light" => ThemeSpec {
+                bg_primary: rgb(255, 254, 252),   // Off-white
+                bg_secondary: rgb(255, 254, 252), // Same as primary
+                bg_accent: rgb(230, 230, 227),    // Light gray for footer
+                fg_primary: rgb(0, 0, 0),         // Black text
+                fg_secondary: rgb(148, 148, 148), // Gray text
+                accent: rgb(234, 68, 57),         // BBC red
+                accent_fg: rgb(255, 255, 255),    // White on red
+                error: Some(ThemeColor(Color::Red)),
+                link: rgb(20, 90, 180),
+                ..ThemeSpec::default()
+            },
+            "dark" => ThemeSpec {
+                bg_primary: rgb(0, 0, 0),         // Pure black
+                bg_secondary: rgb(20, 20, 20),    // Slightly lighter black
+                bg_accent: rgb(40, 40, 40),       // Dark gray for footer
+                fg_primary: rgb(255, 255, 255),   // White text
+                fg_secondary: rgb(150, 150, 150), // Light gray text
+                accent: rgb(234, 68, 57),         // BBC red (same)
+                accent_fg: rgb(255, 255, 255),    // White on red (same)
+                error: Some(ThemeColor(Color::Red)),
+                link: rgb(100, 160, 240),
+                ..ThemeSpec::default()
+            },
+            "solarized-light" => ThemeSpec {
+                bg_primary: rgb(253, 246, 227),   // base3
+                bg_secondary: rgb(238, 232, 213), // base2
+                bg_accent: rgb(238, 232, 213),    // base2
+                fg_primary: rgb(101, 123, 131),   // base00
+                fg_secondary: rgb(147, 161, 161), // base1
+                accent: rgb(220, 50, 47),         // red
+                accent_fg: rgb(253, 246, 227),    // base3
+                error: rgb(220, 50, 47),          // red
+                ticker: rgb(181, 137, 0),         // yellow
+                link: rgb(38, 139, 210),          // blue
+                ..ThemeSpec::default()
+            },
+            "solarized-dark" => ThemeSpec {
+                bg_primary: rgb(0, 43, 54),       // base03
+                bg_secondary: rgb(7, 54, 66),     // base02
+                bg_accent: rgb(7, 54, 66),        // base02
+                fg_primary: rgb(131, 148, 150),   // base0
+                fg_secondary: rgb(88, 110, 117),  // base01
+                accent: rgb(220, 50, 47),         // red
+                accent_fg: rgb(253, 246, 227),    // base3
+                error: rgb(220, 50, 47),          // red
+                ticker: rgb(181, 137, 0),         // yellow
+                link: rgb(38, 139, 210),          // blue
+                ..ThemeSpec::default()
+            },
+            "high-contrast" => ThemeSpec {
+                bg_primary: rgb(0, 0, 0),
+                bg_secondary: rgb(0, 0, 0),
+                bg_accent: rgb(0, 0, 0),
+                fg_primary: rgb(255, 255, 255),
+                fg_secondary: rgb(200, 200, 200),
+                accent: rgb(255, 255, 0),         // Yellow, with black text
+                accent_fg: rgb(0, 0, 0),
+                error: rgb(255, 90, 90),
+                link: rgb(0, 255, 255),
+                ..ThemeSpec::default()
+            },
+            _ => return None,
+        };
+        Some(spec.resolve(name))
+    }
+
+    /// Find a theme by config name among the available ones, falling back to light
+    pub fn from_name(name: &ThemeName, themes: &[Theme]) -> Self {
+        themes.iter()
+            .find(|theme| theme.name == name.0)
+            .cloned()
+            .or_else(|| Self::built_in(&name.0))
+            .unwrap_or_else(Self::light)
+    }
+
+    /// Config name of this theme
+    pub fn config_name(&self) -> ThemeName {
+        ThemeName::new(&self.name)
+    }
+
+    /// The theme as the terminal can show it. Monochrome keeps only the name.
+    pub fn for_depth(self, depth: ColorDepth) -> Self {
+        let reduce = |color| depth.reduce(color);
         Self {
-            name: "Dark".to_string(),
-            bg_primary: Color::Rgb(0, 0, 0),         // Pure black
-            bg_secondary: Color::Rgb(20, 20, 20),    // Slightly lighter black
-            bg_accent: Color::Rgb(40, 40, 40),       // Dark gray for footer
-            fg_primary: Color::Rgb(255, 255, 255),   // White text
-            fg_secondary: Color::Rgb(150, 150, 150), // Light gray text
-            accent: Color::Rgb(234, 68, 57),         // BBC red (same)
-            accent_fg: Color::Rgb(255, 255, 255),    // White on red (same)
+            bg_primary: reduce(self.bg_primary),
+            bg_secondary: reduce(self.bg_secondary),
+            bg_accent: reduce(self.bg_accent),
+            fg_primary: reduce(self.fg_primary),
+            fg_secondary: reduce(self.fg_secondary),
+            accent: reduce(self.accent),
+            accent_fg: reduce(self.accent_fg),
+            selection_bg: reduce(self.selection_bg),
+            selection_fg: reduce(self.selection_fg),
+            error: reduce(self.error),
+            header_bg: reduce(self.header_bg),
+            header_fg: reduce(self.header_fg),
+            ticker: reduce(self.ticker),
+            link: reduce(self.link),
+            read: reduce(self.read),
+            monochrome: self.monochrome || depth == ColorDepth::Monochrome,
+            name: self.name,
+        }
+    }
+
+    /// Selected story or menu item
+    pub fn selection_style(&self) -> Style {
+        self.highlight(Style::default().fg(self.selection_fg).bg(self.selection_bg).add_modifier(Modifier::BOLD))
+    }
+
+    /// Header and tab bar
+    pub fn header_style(&self) -> Style {
+        self.highlight(Style::default().fg(self.header_fg).bg(self.header_bg))
+    }
+
+    /// Current tab, the header colours swapped
+    pub fn active_tab_style(&self) -> Style {
+        let style = Style::default().fg(self.header_bg).bg(self.header_fg).add_modifier(Modifier::BOLD);
+        if self.monochrome {
+            // Drawn over the reversed header, so stands out by not being reversed
+            style.remove_modifier(Modifier::REVERSED).add_modifier(Modifier::UNDERLINED)
+        } else {
+            style
+        }
+    }
+
+    /// Search matches in the article
+    pub fn match_style(&self) -> Style {
+        self.highlight(Style::default().fg(self.accent_fg).bg(self.accent))
+    }
+
+    /// Footer bar
+    pub fn footer_style(&self) -> Style {
+        self.highlight(Style::default().fg(self.fg_primary).bg(self.bg_accent))
+    }
+
+    /// Error messages
+    pub fn error_style(&self) -> Style {
+        let style = Style::default().fg(self.error).bg(self.bg_primary);
+        if self.monochrome { style.add_modifier(Modifier::BOLD) } else { style }
+    }
+
+    // Without colours, filled areas are drawn in reverse video
+    fn highlight(&self, style: Style) -> Style {
+        if self.monochrome { style.add_modifier(Modifier::REVERSED) } else { style }
+    }
+}
+
+fn rgb(r: u8, g: u8, b: u8) -> Option<ThemeColor> {
+    Some(ThemeColor(Color::Rgb(r, g, b)))
+}
+
+/// A colour as written in a theme: a name ("red", "dark gray"), "#rrggbb" or a 256-colour index
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemeColor(pub Color);
+
+impl<'de> Deserialize<'de> for ThemeColor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Color::from_str(&value)
+            .map(ThemeColor)
+            .map_err(|_| serde::de::Error::custom(format!("unknown colour `{}`", value)))
+    }
+}
+
+impl Serialize for ThemeColor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+/// A theme as written in config or a theme file. Slots left out come from the `base` theme,
+/// and the extra slots follow the main ones: selection and header use the accent colours,
+/// the ticker uses the main text colour and read stories the secondary one.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct ThemeSpec {
+    /// Built-in theme to start from (default "light")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<ThemeName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_primary: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_secondary: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_accent: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg_primary: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg_secondary: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accent_fg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_bg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_fg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_bg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header_fg: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticker: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<ThemeColor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read: Option<ThemeColor>,
+}
+
+impl ThemeSpec {
+    /// Build the theme, filling in slots from the base theme
+    pub fn build(&self, name: &str) -> Result<Theme> {
+        let base_name = self.base.clone().unwrap_or_default();
+        let base = Theme::built_in(&base_name.0)
+            .with_context(|| format!("unknown base theme `{}`, expected one of: {}", base_name, BUILT_IN_THEMES.join(", ")))?;
+        let color = |slot: Option<ThemeColor>, base: Color| slot.map_or(base, |color| color.0);
+
+        // Extra slots only come from the base when the main slot they follow isn't overridden
+        let follow = |slot: Option<ThemeColor>, main: Option<ThemeColor>, base: Color| {
+            slot.or(main).map_or(base, |color| color.0)
+        };
+
+        Ok(Theme {
+            name: name.to_string(),
+            bg_primary: color(self.bg_primary, base.bg_primary),
+            bg_secondary: color(self.bg_secondary, base.bg_secondary),
+            bg_accent: color(self.bg_accent, base.bg_accent),
+            fg_primary: color(self.fg_primary, base.fg_primary),
+            fg_secondary: color(self.fg_secondary, base.fg_secondary),
+            accent: color(self.accent, base.accent),
+            accent_fg: color(self.accent_fg, base.accent_fg),
+            selection_bg: follow(self.selection_bg, self.accent, base.selection_bg),
+            selection_fg: follow(self.selection_fg, self.accent_fg, base.selection_fg),
+            error: color(self.error, base.error),
+            header_bg: follow(self.header_bg, self.accent, base.header_bg),
+            header_fg: follow(self.header_fg, self.accent_fg, base.header_fg),
+            ticker: follow(self.ticker, self.fg_primary, base.ticker),
+            link: color(self.link, base.link),
+            read: follow(self.read, self.fg_secondary, base.read),
+            monochrome: false,
+        })
+    }
+
+    // Built-in themes have every main slot, so the extra ones are filled in from those
+    fn resolve(&self, name: &str) -> Theme {
+        let main = |slot: Option<ThemeColor>| slot.map_or(Color::Reset, |color| color.0);
+        let extra = |slot: Option<ThemeColor>, main_slot: Option<ThemeColor>| main(slot.or(main_slot));
+        Theme {
+            name: name.to_string(),
+            bg_primary: main(self.bg_primary),
+            bg_secondary: main(self.bg_secondary),
+            bg_accent: main(self.bg_accent),
+            fg_primary: main(self.fg_primary),
+            fg_secondary: main(self.fg_secondary),
+            accent: main(self.accent),
+            accent_fg: main(self.accent_fg),
+            selection_bg: extra(self.selection_bg, self.accent),
+            selection_fg: extra(self.selection_fg, self.accent_fg),
+            error: extra(self.error, self.accent),
+            header_bg: extra(self.header_bg, self.accent),
+            header_fg: extra(self.header_fg, self.accent_fg),
+            ticker: extra(self.ticker, self.fg_primary),
+            link: extra(self.link, self.accent),
+            read: extra(self.read, self.fg_secondary),
+            monochrome: false,
+        }
+    }
+}
+
+/// Every theme that can be picked: the built-ins, then `themes/*.toml` files, then `[themes]`
+/// tables from the config. A user theme with the name of an earlier one replaces it.
+/// Broken theme files are skipped with a warning.
+pub fn load_themes(configured: &BTreeMap<String, ThemeSpec>, dir: Option<&Path>) -> (Vec<Theme>, Vec<String>) {
+    let mut themes: Vec<Theme> = BUILT_IN_THEMES.iter().filter_map(|name| Theme::built_in(name)).collect();
+    let mut warnings = Vec::new();
+
+    let mut add = |theme: Theme| match themes.iter_mut().find(|existing| existing.name == theme.name) {
+        Some(existing) => *existing = theme,
+        None => themes.push(theme),
+    };
+
+    let mut files: Vec<_> = dir
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+    for path in files {
+        let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let loaded = load_theme_file(&path).and_then(|(spec, unknown_keys)| Ok((spec.build(name)?, unknown_keys)));
+        match loaded {
+            Ok((theme, unknown_keys)) => {
+                add(theme);
+                warnings.extend(unknown_keys.into_iter().map(|key| format!("theme file {}: {}", path.display(), key)));
+            }
+            Err(e) => warnings.push(format!("theme file {}: {:#}", path.display(), e)),
         }
     }
 
-    pub fn from_name(name: &ThemeName) -> Self {
-        match name {
-            ThemeName::Light => Self::light(),
-            ThemeName::Dark => Self::dark(),
+    for (name, spec) in configured {
+        match spec.build(name) {
+            Ok(theme) => add(theme),
+            Err(e) => warnings.push(format!("themes.{}: {:#}", name, e)),
         }
     }
+
+    (themes, warnings)
+}
+
+// The theme in a file, and a warning for each key it doesn't recognise, like the config file
+fn load_theme_file(path: &Path) -> Result<(ThemeSpec, Vec<String>)> {
+    let content = std::fs::read_to_string(path)?;
+    let mut unknown_keys = Vec::new();
+    let spec = serde_ignored::deserialize(toml::Deserializer::new(&content), |key| unknown_keys.push(key.to_string()))
+        .map_err(|e| anyhow::anyhow!("{}", e.to_string().trim_end()))?;
+
+    let warnings = unknown_keys
+        .into_iter()
+        .map(|key| match crate::config::find_key_line(&content, &key) {
+            Some(line) => format!("line {}: unknown key `{}`", line, key),
+            None => format!("unknown key `{}`", key),
+        })
+        .collect();
+    Ok((spec, warnings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_built_in_themes() {
+        for name in BUILT_IN_THEMES {
+            let theme = Theme::built_in(name).unwrap();
+            assert_eq!(theme.name, name);
+            assert_ne!(theme.bg_primary, theme.fg_primary, "{}", name);
+        }
+        assert_eq!(Theme::light().selection_bg, Theme::light().accent);
+        assert!(Theme::built_in("purple").is_none());
+    }
+
+    #[test]
+    fn test_monochrome_uses_reverse_video() {
+        let theme = Theme::dark().for_depth(ColorDepth::Monochrome);
+        assert_eq!(theme.name, "dark");
+        assert_eq!(theme.accent, Color::Reset);
+        assert!(theme.selection_style().add_modifier.contains(Modifier::REVERSED));
+        assert!(!Theme::dark().selection_style().add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_user_theme_starts_from_base() {
+        let spec: ThemeSpec = toml::from_str("base = \"dark\"\naccent = \"#268bd2\"\nread = \"dark gray\"\n").unwrap();
+        let theme = spec.build("mine").unwrap();
+        let dark = Theme::dark();
+
+        assert_eq!(theme.bg_primary, dark.bg_primary);
+        assert_eq!(theme.accent, Color::Rgb(0x26, 0x8b, 0xd2));
+        // Selection follows the new accent, the read colour is set directly
+        assert_eq!(theme.selection_bg, theme.accent);
+        assert_eq!(theme.read, Color::DarkGray);
+
+        let spec: ThemeSpec = toml::from_str("base = \"mauve\"\n").unwrap();
+        assert!(spec.build("mine").is_err());
+        assert!(toml::from_str::<ThemeSpec>("accent = \"not a colour\"\n").is_err());
+    }
+
+    #[test]
+    fn test_load_themes_from_files_and_config() {
+        let dir = std::env::temp_dir().join(format!("bbcli-themes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("paper.toml"), "bg_primary = \"white\"\n").unwrap();
+        std::fs::write(dir.join("broken.toml"), "bg_primary = 3.5\n").unwrap();
+        std::fs::write(dir.join("typo.toml"), "base = \"dark\"\nacent = \"red\"\n").unwrap();
+
+        let configured = BTreeMap::from([
+            (String::from("dark"), toml::from_str("base = \"dark\"\nfg_primary = \"yellow\"\n").unwrap()),
+            (String::from("night"), ThemeSpec { base: Some(ThemeName::new("dark")), ..ThemeSpec::default() }),
+        ]);
+        let (themes, warnings) = load_themes(&configured, Some(&dir));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+        assert_eq!(names, ["light", "dark", "solarized-light", "solarized-dark", "high-contrast", "paper", "typo", "night"]);
+        assert_eq!(themes[1].fg_primary, Color::Yellow);
+        assert_eq!(warnings.len(), 2, "{:?}", warnings);
+        assert!(warnings[0].contains("broken.toml"), "{:?}", warnings);
+        // A misspelt key still loads the theme, with a warning like the config file gives
+        assert!(warnings[1].contains("typo.toml") && warnings[1].ends_with("line 2: unknown key `acent`"), "{:?}", warnings);
+    }
 }
@@ -1,60 +1,220 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Constraint, Direction, Layout, Margin, Rect, Size},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Clear},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Clear, Scrollbar, ScrollbarOrientation, ScrollbarState},
     Frame,
 };
 use ratatui_image::{picker::Picker, StatefulImage};
 
-use crate::app::{App, AppMode, ImageProtocol};
+use crate::app::{App, AppMode, ImageProtocol, NewsStory, PreviewPosition};
 use crate::date_utils::humanize_time;
-use crate::feeds::get_all_feeds;
+use crate::feeds::get_menu_feeds;
 use crate::image_cache::get_image;
+use crate::input::MouseTarget;
+use crate::keymap::{Action, KeyMode};
+use crate::palette::Palette;
+use crate::reader;
+use crate::text_layout;
 use ratatui_image::picker::ProtocolType;
 
 pub fn render(f: &mut Frame, app: &App) {
-    let main_chunks = Layout::default()
+    let main_chunks = main_layout(f.area());
+
+    render_header(f, main_chunks[0], app);
+
+    let areas = content_layout(app, main_chunks[1]);
+    if let Some(sidebar_area) = areas.sidebar {
+        render_sidebar(f, sidebar_area, app);
+    }
+    if let Some(stories_area) = areas.stories {
+        render_stories(f, stories_area, app);
+    }
+    if let Some(reader_area) = areas.reader {
+        if app.show_full_article {
+            render_full_article(f, reader_area, app);
+        } else {
+            render_preview(f, reader_area, app, areas.stacked);
+        }
+    }
+
+    render_footer(f, main_chunks[2], app);
+
+    // Render feed menu overlay if in feed menu mode
+    if app.mode == AppMode::FeedMenu {
+        render_feed_menu(f, app);
+    }
+
+    // Render help menu overlay if in help mode
+    if app.mode == AppMode::Help {
+        render_help_menu(f, app);
+    }
+
+    if let Some(palette) = app.palette.as_ref().filter(|_| app.mode == AppMode::Palette) {
+        render_palette(f, app, palette);
+    }
+}
+
+fn main_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),  // Header
             Constraint::Min(0),     // Main content
             Constraint::Length(1),  // Footer (no border)
         ])
-        .split(f.area());
+        .split(area)
+}
 
-    render_header(f, main_chunks[0], app);
+// Where the parts of the content area go. The reader shows the full article when
+// it's open, and the preview otherwise.
+struct ContentAreas {
+    sidebar: Option<Rect>,
+    stories: Option<Rect>,
+    reader: Option<Rect>,
+    stacked: bool,  // Reader is below the story list
+}
 
-    // Full article view takes over the entire content area (full width)
-    if app.show_full_article {
-        render_full_article(f, main_chunks[1], app);
-    } else if app.show_preview {
-        // Split main content area if preview is enabled (but not full article)
-        let content_chunks = Layout::default()
+fn content_layout(app: &App, area: Rect) -> ContentAreas {
+    if app.is_panes() {
+        let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(80),  // Story list
-                Constraint::Percentage(20),  // Preview
+                Constraint::Length(app.layout.sidebar_width),  // Feed sidebar
+                Constraint::Min(0),                            // Story list and reading pane
             ])
-            .split(main_chunks[1]);
+            .split(area);
+        let stacked = is_stacked(app, area.width);
+        let panes = split_reader(chunks[1], 100 - app.layout.list_percent.min(100), stacked);
+        return ContentAreas { sidebar: Some(chunks[0]), stories: Some(panes[0]), reader: Some(panes[1]), stacked };
+    }
 
-        render_stories(f, content_chunks[0], app);
-        render_preview(f, content_chunks[1], app);
-    } else {
-        render_stories(f, main_chunks[1], app);
+    // Full article view takes over the entire content area (full width)
+    if app.show_full_article {
+        return ContentAreas { sidebar: None, stories: None, reader: Some(area), stacked: false };
     }
+    if !app.show_preview {
+        return ContentAreas { sidebar: None, stories: Some(area), reader: None, stacked: false };
+    }
+    let stacked = is_stacked(app, area.width);
+    let content_chunks = split_reader(area, app.layout.preview_percent.min(100), stacked);
+    ContentAreas { sidebar: None, stories: Some(content_chunks[0]), reader: Some(content_chunks[1]), stacked }
+}
 
-    render_footer(f, main_chunks[2], app);
+// Whether the preview or reading pane goes below the story list rather than beside it
+fn is_stacked(app: &App, width: u16) -> bool {
+    app.layout.preview_position == PreviewPosition::Bottom || width < app.layout.stack_below
+}
 
-    // Render feed menu overlay if in feed menu mode
-    if app.mode == AppMode::FeedMenu {
-        render_feed_menu(f, app);
+// Story list, then the preview or reading pane with `reader_percent` of the area
+fn split_reader(area: Rect, reader_percent: u16, stacked: bool) -> std::rc::Rc<[Rect]> {
+    let direction = if stacked { Direction::Vertical } else { Direction::Horizontal };
+    Layout::default()
+        .direction(direction)
+        .constraints([
+            Constraint::Min(0),                      // Story list
+            Constraint::Percentage(reader_percent),  // Preview or reading pane
+        ])
+        .split(area)
+}
+
+// Footer split into status/ticker and clock
+fn footer_layout(area: Rect) -> std::rc::Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Min(0),      // Left: status/ticker
+            Constraint::Length(8),   // Right: time (HH:MM:SS)
+        ])
+        .split(area)
+}
+
+// Centered feed menu popup
+fn feed_menu_area(area: Rect, feed_count: usize) -> Rect {
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = (feed_count as u16 + 4).min(area.height.saturating_sub(4));
+    Rect {
+        x: (area.width.saturating_sub(popup_width)) / 2,
+        y: (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
     }
+}
 
-    // Render help menu overlay if in help mode
-    if app.mode == AppMode::Help {
-        render_help_menu(f, app);
+// First item a List shows. The list state isn't kept between frames, so ratatui scrolls
+// just far enough to keep the selection on screen.
+fn list_offset(selected: usize, item_height: u16, rows: u16) -> usize {
+    let visible = (rows / item_height.max(1)).max(1) as usize;
+    (selected + 1).saturating_sub(visible)
+}
+
+/// Find what's drawn at a screen position, using the same layout as `render`
+pub fn mouse_target(app: &App, size: Size, column: u16, row: u16) -> Option<MouseTarget> {
+    let area = Rect::new(0, 0, size.width, size.height);
+    let position = ratatui::layout::Position::new(column, row);
+
+    // Overlays take every click while they're open
+    match app.mode {
+        AppMode::Help | AppMode::Palette => return None,
+        AppMode::FeedMenu => {
+            let feed_count = get_menu_feeds().len();
+            let inner = Block::default().borders(Borders::ALL).inner(feed_menu_area(area, feed_count));
+            if !inner.contains(position) {
+                return Some(MouseTarget::FeedMenu).filter(|_| feed_menu_area(area, feed_count).contains(position));
+            }
+            let index = list_offset(app.feed_menu_selected, 1, inner.height) + (row - inner.y) as usize;
+            return Some(MouseTarget::Feed(index)).filter(|_| index < feed_count);
+        }
+        _ => {}
+    }
+
+    let main_chunks = main_layout(area);
+    if let Some(tab_area) = tab_bar_area(app, main_chunks[0]).filter(|tab_area| tab_area.contains(position)) {
+        let mut end = tab_area.x;
+        return tab_labels(app).iter().position(|label| {
+            end += text_layout::width(label) as u16;
+            column < end
+        }).map(MouseTarget::Tab);
+    }
+    if footer_layout(main_chunks[2])[0].contains(position) {
+        return footer_ticker(app).map(|_| MouseTarget::Ticker);
+    }
+    if !main_chunks[1].contains(position) {
+        return None;
     }
+
+    let areas = content_layout(app, main_chunks[1]);
+    if let Some(sidebar) = areas.sidebar.filter(|sidebar| sidebar.contains(position)) {
+        let inner = Block::default().borders(Borders::ALL).inner(sidebar);
+        if !inner.contains(position) {
+            return None;
+        }
+        let index = list_offset(app.feed_menu_selected, 1, inner.height) + (row - inner.y) as usize;
+        return Some(MouseTarget::Feed(index)).filter(|_| index < get_menu_feeds().len());
+    }
+    if areas.reader.is_some_and(|reader| reader.contains(position)) {
+        return Some(if app.show_full_article { MouseTarget::Article } else { MouseTarget::Preview });
+    }
+    let stories_area = areas.stories?;
+    let index = list_offset(app.selected, 2, stories_area.height) + ((row - stories_area.y) / 2) as usize;
+    // Nothing to click while the list shows a loading or error message
+    Some(MouseTarget::Story(index))
+        .filter(|_| index < app.stories.len() && !app.is_loading && app.error_message.is_none())
+}
+
+/// Height of the story list, which is shorter with the preview below it
+pub fn list_height(app: &App, size: Size) -> u16 {
+    let content = main_layout(Rect::new(0, 0, size.width, size.height))[1];
+    content_layout(app, content).stories.map_or(content.height, |stories| stories.height)
+}
+
+/// Text area of the full article: the article column inside the reading pane in the
+/// three-pane layout, otherwise inside the whole content area
+pub fn article_view(app: &App, size: Size) -> Size {
+    let content = main_layout(Rect::new(0, 0, size.width, size.height))[1];
+    let area = if app.is_panes() { content_layout(app, content).reader.unwrap_or(content) } else { content };
+    let column = article_column(Block::default().borders(Borders::ALL).inner(area), app.reader.max_width);
+    Size::new(column.width, column.height)
 }
 
 fn render_header(f: &mut Frame, area: Rect, app: &App) {
@@ -65,29 +225,41 @@ fn render_header(f: &mut Frame, area: Rect, app: &App) {
         } else {
             date_str.to_string()
         };
-        format!("Last updated: {} | {}", formatted_date, app.current_feed.name)
+        format!("Last updated: {} | {} | {} unread", formatted_date, app.current_feed.name, app.unread_count())
     } else {
         format!("Last updated: -- | {}", app.current_feed.name)
     };
 
-    // Add offline indicator if in offline mode
-    let title_text = if app.is_offline {
-        "BBC | NEWS [OFFLINE]"
+    // Show active search filter
+    let last_updated = if app.is_search_active() {
+        format!("{} | /{} ({} matches)", last_updated, app.search_query, app.search_matches.len())
     } else {
-        "BBC | NEWS"
+        last_updated
+    };
+
+    // Add offline indicator if in offline mode
+    let title_text = match (app.is_offline, app.offline_since) {
+        (true, Some(since)) => format!("BBC | NEWS [Offline edition from {}]", since.format("%H:%M")),
+        (true, None) => String::from("BBC | NEWS [OFFLINE]"),
+        (false, _) => String::from("BBC | NEWS"),
     };
 
+    // Long feed names and queries end in "..." rather than being clipped at both edges
+    let width = area.width as usize;
+    let title_text = text_layout::truncate(&title_text, width);
+    let last_updated = text_layout::truncate(&last_updated, width);
+
     let header_text = vec![
         Line::from(
             Span::styled(
                 title_text,
-                Style::default().fg(app.theme.accent_fg).add_modifier(Modifier::BOLD)
+                Style::default().fg(app.theme.header_fg).add_modifier(Modifier::BOLD)
             )
         ),
         Line::from(
             Span::styled(
                 last_updated,
-                Style::default().fg(app.theme.accent_fg)
+                Style::default().fg(app.theme.header_fg)
             )
         ),
     ];
@@ -96,9 +268,54 @@ fn render_header(f: &mut Frame, area: Rect, app: &App) {
         .alignment(Alignment::Center)
         .block(Block::default()
             .borders(Borders::NONE)
-            .style(Style::default().bg(app.theme.accent)));
+            .style(app.theme.header_style()));
 
     f.render_widget(header, area);
+
+    if let Some(tab_area) = tab_bar_area(app, area) {
+        render_tab_bar(f, tab_area, app);
+    }
+}
+
+// Last row of the header, when more than one feed is open
+fn tab_bar_area(app: &App, header: Rect) -> Option<Rect> {
+    (app.tabs.len() > 1 && header.height >= 3)
+        .then(|| Rect { y: header.y + header.height - 1, height: 1, ..header })
+}
+
+// Tab labels as drawn, like " 2 World (5) ". The unread count covers stories already loaded.
+fn tab_labels(app: &App) -> Vec<String> {
+    app.tab_names()
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let unread = if i == app.active_tab {
+                app.unread_count()
+            } else {
+                app.read_state.unread_count(&app.tabs[i].stories)
+            };
+            match unread {
+                0 => format!(" {} {} ", i + 1, name),
+                unread => format!(" {} {} ({}) ", i + 1, name, unread),
+            }
+        })
+        .collect()
+}
+
+fn render_tab_bar(f: &mut Frame, area: Rect, app: &App) {
+    let spans: Vec<Span> = tab_labels(app)
+        .into_iter()
+        .enumerate()
+        .map(|(i, label)| {
+            let style = if i == app.active_tab {
+                app.theme.active_tab_style()
+            } else {
+                app.theme.header_style()
+            };
+            Span::styled(label, style)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
 fn render_stories(f: &mut Frame, area: Rect, app: &App) {
@@ -113,7 +330,7 @@ fn render_stories(f: &mut Frame, area: Rect, app: &App) {
     // Show error message if present
     if let Some(ref error_msg) = app.error_message {
         let error_text = vec![
-            Line::from(Span::styled("Error fetching BBC News:", Style::default().fg(Color::Red).bg(app.theme.bg_primary).add_modifier(Modifier::BOLD))),
+            Line::from(Span::styled("Error fetching BBC News:", app.theme.error_style().add_modifier(Modifier::BOLD))),
             Line::from(""),
             Line::from(Span::styled(error_msg, Style::default().fg(app.theme.fg_primary).bg(app.theme.bg_primary))),
             Line::from(""),
@@ -128,7 +345,7 @@ fn render_stories(f: &mut Frame, area: Rect, app: &App) {
 
     if app.stories.is_empty() {
         let empty = Paragraph::new("No stories available. Press 'r' to refresh.")
-            .style(Style::default().fg(Color::Red).bg(app.theme.bg_primary))
+            .style(app.theme.error_style())
             .alignment(Alignment::Center);
         f.render_widget(empty, area);
         return;
@@ -142,31 +359,27 @@ fn render_stories(f: &mut Frame, area: Rect, app: &App) {
             let is_selected = i == app.selected;
             let number = i + 1;
 
-            let title_text = format!("{}. {}", number, story.title);
+            // Star marks bookmarked stories
+            let marker = if app.is_bookmarked(i) { "★ " } else { "" };
+            let title_text = format!("{}. {}{}", number, marker, story.title);
 
             // Pad title to full width for full-width background, truncate if too long
             let width = area.width as usize;
-            let padded_title = if title_text.len() < width {
-                format!("{}{}", title_text, " ".repeat(width - title_text.len()))
-            } else {
-                // Truncate and add ellipsis
-                let max_len = width.saturating_sub(3);
-                if max_len > 0 {
-                    let truncated = title_text.chars().take(max_len).collect::<String>();
-                    format!("{}...", truncated)
-                } else {
-                    title_text.chars().take(width).collect::<String>()
-                }
-            };
+            let padded_title = text_layout::fit(&title_text, width);
 
             let title_line = if is_selected {
-                // Selected: white text on accent (BBC red) background (full width)
+                // Selected: selection colours across the full width
+                Line::styled(
+                    padded_title,
+                    app.theme.selection_style()
+                )
+            } else if app.is_read(i) || (app.is_search_active() && !app.is_search_match(i)) {
+                // Read story, or search active but not a match: dimmed text
                 Line::styled(
                     padded_title,
                     Style::default()
-                        .fg(app.theme.accent_fg)
-                        .bg(app.theme.accent)
-                        .add_modifier(Modifier::BOLD)
+                        .fg(app.theme.read)
+                        .bg(app.theme.bg_primary)
                 )
             } else {
                 // Normal: primary text on primary background
@@ -186,18 +399,7 @@ fn render_stories(f: &mut Frame, area: Rect, app: &App) {
                 story.pub_date.clone()
             };
             let meta_text = format!("   Last updated: {} | {}", formatted_date, app.current_feed.name);
-            let padded_meta = if meta_text.len() < width {
-                format!("{}{}", meta_text, " ".repeat(width - meta_text.len()))
-            } else {
-                // Truncate and add ellipsis
-                let max_len = width.saturating_sub(3);
-                if max_len > 0 {
-                    let truncated = meta_text.chars().take(max_len).collect::<String>();
-                    format!("{}...", truncated)
-                } else {
-                    meta_text.chars().take(width).collect::<String>()
-                }
-            };
+            let padded_meta = text_layout::fit(&meta_text, width);
 
             let meta_line = Line::styled(
                 padded_meta,
@@ -226,69 +428,158 @@ fn render_footer(f: &mut Frame, area: Rect, app: &App) {
     use chrono::Local;
 
     // Split footer into left and right sections
-    let footer_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Min(0),      // Left: status/ticker
-            Constraint::Length(8),   // Right: time (HH:MM:SS)
-        ])
-        .split(area);
+    let footer_chunks = footer_layout(area);
+
+    // Config problems replace the ticker, but not prompts or refresh status
+    let config_message = app.config_message.as_ref()
+        .filter(|_| !matches!(app.mode, AppMode::Search | AppMode::ArchiveSearch | AppMode::ArticleSearch) && !app.is_refreshing);
 
     // Left side: show refresh status or ticker/keybindings
-    let footer_text = if app.is_refreshing {
+    let mut is_ticker = false;
+    let footer_text = if app.mode == AppMode::Search {
+        // Search prompt with cursor
+        format!("/{}_", app.search_query)
+    } else if app.mode == AppMode::ArchiveSearch {
+        format!("Search archive: {}_", app.archive_query)
+    } else if app.mode == AppMode::ArticleSearch {
+        format!("Search article: {}_", app.article_query)
+    } else if let Some(story) = app.stories.get(app.selected).filter(|_| app.confirm_open) {
+        format!("Open \"{}\" in the browser? y to open, any other key to cancel", story.title)
+    } else if app.is_refreshing {
         String::from("Refreshing News...")
-    } else if !app.ticker_stories.is_empty() {
+    } else if let Some(message) = config_message {
+        message.clone()
+    } else if !app.pending_keys.is_empty() || app.pending_count.is_some() {
+        // Part way through a count or multi-key sequence
+        let count = app.pending_count.map(|count| count.to_string()).unwrap_or_default();
+        let keys: Vec<String> = app.pending_keys.iter().map(|chord| chord.to_string()).collect();
+        format!("{}{}-", count, keys.join(" "))
+    } else if let Some(story) = footer_ticker(app) {
+        is_ticker = true;
         let max_ticker_items = 8.min(app.ticker_stories.len());
-        if app.ticker_index < app.ticker_stories.len() {
-            let story = &app.ticker_stories[app.ticker_index];
-            format!("[LATEST] {} ({}/{})", story.title, app.ticker_index + 1, max_ticker_items)
-        } else {
-            format!("q: quit | o: open | Tab: preview | f: feeds | s: sort ({}) | t: dates | p: protocol ({}) | r: refresh", app.sort_order.name(), app.image_protocol.name())
-        }
+        // Shorten the headline so the position stays in view
+        let position = format!(" ({}/{})", app.ticker_index + 1, max_ticker_items);
+        let room = (footer_chunks[0].width as usize).saturating_sub(text_layout::width("[LATEST] ") + text_layout::width(&position));
+        format!("[LATEST] {}{}", text_layout::truncate(&story.title, room), position)
     } else {
         // Show default keybindings help when no ticker stories
-        format!("q: quit | o: open | Tab: preview | f: feeds | s: sort ({}) | t: dates | p: protocol ({}) | r: refresh", app.sort_order.name(), app.image_protocol.name())
+        footer_hints(app)
     };
 
+    let footer_fg = if config_message.is_some() {
+        app.theme.error
+    } else if is_ticker {
+        app.theme.ticker
+    } else {
+        app.theme.fg_primary
+    };
     let footer_left = Paragraph::new(footer_text)
-        .style(Style::default().fg(app.theme.fg_primary).bg(app.theme.bg_accent))
+        .style(app.theme.footer_style().fg(footer_fg))
         .alignment(Alignment::Left);
 
     // Right side: current time with seconds
     let current_time = Local::now().format("%H:%M:%S").to_string();
     let footer_right = Paragraph::new(current_time)
-        .style(Style::default().fg(app.theme.fg_primary).bg(app.theme.bg_accent))
+        .style(app.theme.footer_style())
         .alignment(Alignment::Right);
 
     f.render_widget(footer_left, footer_chunks[0]);
     f.render_widget(footer_right, footer_chunks[1]);
 }
 
+// Ticker story shown in the footer, unless a prompt or message has taken its place
+fn footer_ticker(app: &App) -> Option<&NewsStory> {
+    let replaced = app.mode == AppMode::Search
+        || app.mode == AppMode::ArchiveSearch
+        || app.confirm_open
+        || app.is_refreshing
+        || app.config_message.is_some()
+        || !app.pending_keys.is_empty()
+        || app.pending_count.is_some();
+    if replaced { None } else { app.ticker_stories.get(app.ticker_index) }
+}
+
+// Key hints for the footer, using whatever keys are bound
+fn footer_hints(app: &App) -> String {
+    let hints = [
+        (Action::Quit, String::from("quit")),
+        (Action::Open, String::from("open")),
+        (Action::Preview, String::from("preview")),
+        (Action::FeedMenu, String::from("feeds")),
+        (Action::Sort, format!("sort ({})", app.sort_order.name())),
+        (Action::DateFormat, String::from("dates")),
+        (Action::ImageProtocol, format!("protocol ({})", app.image_protocol.name())),
+        (Action::Refresh, String::from("refresh")),
+    ];
+
+    hints
+        .into_iter()
+        .filter_map(|(action, label)| {
+            app.keymap.hint(KeyMode::Normal, action).map(|key| format!("{}: {}", key, label))
+        })
+        .collect::<Vec<_>>()
+        .join(" | ")
+}
+
 fn render_full_article(f: &mut Frame, area: Rect, app: &App) {
     if let Some(_story) = app.stories.get(app.selected) {
-        let article_block = Block::default()
-            .title("Article View (Enter/Tab/Esc to close)")
+        let article_text = app.get_current_article_text();
+        let title = match article_text.map(|text| reader::reading_time(text)) {
+            Some((words, minutes)) => format!("Article View: {} min read, {} words (Enter/Tab/Esc to close)", minutes, words),
+            None => String::from("Article View (Enter/Tab/Esc to close)"),
+        };
+        let mut article_block = Block::default()
+            .title(title)
             .borders(Borders::ALL)
             .border_style(Style::default().fg(app.theme.accent))
             .style(Style::default().bg(app.theme.bg_primary));
 
         // Get inner area (within borders)
         let inner_area = article_block.inner(area);
-        f.render_widget(article_block, area);
-
-        if let Some(article_text) = app.get_current_article_text() {
-            // Create paragraph with full text and let ratatui handle wrapping
-            let article_paragraph = Paragraph::new(article_text.as_str())
-                .wrap(ratatui::widgets::Wrap { trim: true })
-                .alignment(Alignment::Left)
-                .style(Style::default().fg(app.theme.fg_primary).bg(app.theme.bg_primary))
-                .scroll((app.article_scroll_offset as u16, 0));
 
-            f.render_widget(article_paragraph, inner_area);
+        if let Some(article_text) = article_text {
+            let column = article_column(inner_area, app.reader.max_width);
+            let lines = reader::wrap(article_text, column.width as usize, &app.reader);
+            let height = inner_area.height as usize;
+            let max_scroll = reader::max_scroll(lines.len(), height);
+            let scroll = app.article_scroll_offset.min(max_scroll);
+
+            // Search matches and progress in the top right of the border
+            let mut status = format!(" {}% ", reader::progress(scroll, lines.len(), height));
+            if !app.article_query.is_empty() {
+                let matches = reader::match_lines(&lines, &app.article_query).len();
+                status = match app.article_match.filter(|_| matches > 0) {
+                    Some(index) => format!(" match {} of {} |{}", index + 1, matches, status),
+                    None if matches == 0 => format!(" no matches |{}", status),
+                    None => format!(" {} matches |{}", matches, status),
+                };
+            }
+            article_block = article_block.title_top(Line::from(status).right_aligned());
+            f.render_widget(article_block, area);
+
+            let text = Style::default().fg(app.theme.fg_primary).bg(app.theme.bg_primary);
+            let visible: Vec<Line> = lines.iter()
+                .skip(scroll)
+                .take(height)
+                .map(|line| highlight_matches(&line.text, &app.article_query, text, app.theme.match_style()))
+                .collect();
+            f.render_widget(Paragraph::new(visible).style(text), column);
+
+            // Scrollbar over the right border, once there's something to scroll
+            if max_scroll > 0 {
+                let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                    .begin_symbol(None)
+                    .end_symbol(None)
+                    .thumb_style(Style::default().fg(app.theme.accent))
+                    .track_style(Style::default().fg(app.theme.fg_secondary));
+                let mut state = ScrollbarState::new(max_scroll).position(scroll).viewport_content_length(height);
+                f.render_stateful_widget(scrollbar, area.inner(Margin { vertical: 1, horizontal: 0 }), &mut state);
+            }
         } else {
+            f.render_widget(article_block, area);
             // No article content cached - show error message
             let error_msg = Paragraph::new("No article content available.\nPress Tab or Esc to return to preview.")
-                .style(Style::default().fg(Color::Red).bg(app.theme.bg_primary))
+                .style(app.theme.error_style())
                 .alignment(Alignment::Center)
                 .wrap(ratatui::widgets::Wrap { trim: true });
             f.render_widget(error_msg, inner_area);
@@ -296,7 +587,34 @@ fn render_full_article(f: &mut Frame, area: Rect, app: &App) {
     }
 }
 
-fn render_preview(f: &mut Frame, area: Rect, app: &App) {
+// Where the article text goes: at most `max_width` columns, centred in the pane
+fn article_column(inner: Rect, max_width: u16) -> Rect {
+    let width = if max_width == 0 { inner.width } else { inner.width.min(max_width) };
+    Rect { x: inner.x + (inner.width - width) / 2, width, ..inner }
+}
+
+// A line of the article with each search match picked out
+fn highlight_matches<'a>(text: &'a str, query: &str, style: Style, match_style: Style) -> Line<'a> {
+    let matches = reader::find_matches(text, query);
+    if matches.is_empty() {
+        return Line::styled(text, style);
+    }
+
+    // Match ranges are in chars, spans need byte positions
+    let byte_at = |char_index: usize| text.char_indices().nth(char_index).map_or(text.len(), |(i, _)| i);
+    let mut spans = Vec::new();
+    let mut end = 0;
+    for range in matches {
+        let (start, stop) = (byte_at(range.start), byte_at(range.end));
+        spans.push(Span::styled(&text[end..start], style));
+        spans.push(Span::styled(&text[start..stop], match_style));
+        end = stop;
+    }
+    spans.push(Span::styled(&text[end..], style));
+    Line::from(spans)
+}
+
+fn render_preview(f: &mut Frame, area: Rect, app: &App, stacked: bool) {
     if let Some(story) = app.stories.get(app.selected) {
         // Choose title based on loading state
         let title = if app.is_fetching_article {
@@ -339,12 +657,17 @@ fn render_preview(f: &mut Frame, area: Rect, app: &App) {
             return;
         }
 
-        // Split into image area and text area
+        // Split into image area and text area, side by side when the preview is below the list
+        let direction = if stacked {
+            Direction::Horizontal
+        } else {
+            Direction::Vertical
+        };
         let chunks = Layout::default()
-            .direction(Direction::Vertical)
+            .direction(direction)
             .constraints([
-                Constraint::Percentage(40), // Image area
-                Constraint::Percentage(60), // Text area
+                Constraint::Percentage(app.layout.image_percent.min(100)), // Image area
+                Constraint::Min(0),                                        // Text area
             ])
             .split(inner_area);
 
@@ -376,8 +699,8 @@ fn render_preview(f: &mut Frame, area: Rect, app: &App) {
         // Calculate target pixel dimensions based on widget area and font size
         // This ensures the image fits within the allocated space while maintaining aspect ratio
         let font_size = picker.font_size;
-        let target_width = (image_area.width as u32 * font_size.0 as u32) as u32;
-        let target_height = (image_area.height as u32 * font_size.1 as u32) as u32;
+        let target_width = image_area.width as u32 * font_size.0 as u32;
+        let target_height = image_area.height as u32 * font_size.1 as u32;
 
         // Resize image to fit within the widget area while maintaining aspect ratio
         let resized_img = img.resize(target_width, target_height, image::imageops::FilterType::Triangle);
@@ -421,6 +744,10 @@ fn render_preview(f: &mut Frame, area: Rect, app: &App) {
             format!("Feed: {}", app.current_feed.name),
             Style::default().fg(app.theme.fg_secondary)
         )));
+        preview_lines.push(Line::from(Span::styled(
+            story.link.as_str(),
+            Style::default().fg(app.theme.link).add_modifier(Modifier::UNDERLINED)
+        )));
 
         let preview_text = Paragraph::new(preview_lines)
             .wrap(ratatui::widgets::Wrap { trim: true })
@@ -432,19 +759,10 @@ fn render_preview(f: &mut Frame, area: Rect, app: &App) {
 }
 
 fn render_feed_menu(f: &mut Frame, app: &App) {
-    let feeds = get_all_feeds();
+    let feeds = get_menu_feeds();
 
     // Create centered popup
-    let area = f.area();
-    let popup_width = 60.min(area.width - 4);
-    let popup_height = (feeds.len() as u16 + 4).min(area.height - 4);
-
-    let popup_area = Rect {
-        x: (area.width.saturating_sub(popup_width)) / 2,
-        y: (area.height.saturating_sub(popup_height)) / 2,
-        width: popup_width,
-        height: popup_height,
-    };
+    let popup_area = feed_menu_area(f.area(), feeds.len());
 
     // Clear the area
     f.render_widget(Clear, popup_area);
@@ -458,13 +776,13 @@ fn render_feed_menu(f: &mut Frame, app: &App) {
             let is_current = feed.name == app.current_feed.name;
 
             let indicator = if is_current { "✓ " } else { "  " };
-            let text = format!("{}{}", indicator, feed.name);
+            let text = match app.feed_unread_counts.get(&feed.url) {
+                Some(count) => format!("{}{} ({} unread)", indicator, feed.name, count),
+                None => format!("{}{}", indicator, feed.name),
+            };
 
             let style = if is_selected {
-                Style::default()
-                    .fg(app.theme.accent_fg)
-                    .bg(app.theme.accent)
-                    .add_modifier(Modifier::BOLD)
+                app.theme.selection_style()
             } else {
                 Style::default()
                     .fg(app.theme.fg_primary)
@@ -477,7 +795,7 @@ fn render_feed_menu(f: &mut Frame, app: &App) {
 
     let list = List::new(items)
         .block(Block::default()
-            .title("Select Feed (f/Esc to close, Enter to select)")
+            .title("Select Feed (f/Esc to close, Enter to select, t for a new tab)")
             .borders(Borders::ALL)
             .border_style(Style::default().fg(app.theme.accent))
             .style(Style::default().bg(app.theme.bg_primary)));
@@ -488,11 +806,113 @@ fn render_feed_menu(f: &mut Frame, app: &App) {
     f.render_stateful_widget(list, popup_area, &mut state);
 }
 
+// Feed list down the left of the three-pane layout
+fn render_sidebar(f: &mut Frame, area: Rect, app: &App) {
+    let focused = app.is_sidebar_focused();
+    let feeds = get_menu_feeds();
+
+    let items: Vec<ListItem> = feeds
+        .iter()
+        .enumerate()
+        .map(|(i, feed)| {
+            let is_current = feed.url == app.current_feed.url;
+            let indicator = if is_current { "✓ " } else { "  " };
+            // The current feed's count follows reads as they happen
+            let unread = if is_current { Some(app.unread_count()) } else { app.feed_unread_counts.get(&feed.url).copied() };
+            let text = match unread {
+                Some(count) if count > 0 => format!("{}{} ({})", indicator, feed.name, count),
+                _ => format!("{}{}", indicator, feed.name),
+            };
+
+            let style = if focused && i == app.feed_menu_selected {
+                app.theme.selection_style()
+            } else if is_current {
+                Style::default()
+                    .fg(app.theme.fg_primary)
+                    .bg(app.theme.bg_primary)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .fg(app.theme.fg_primary)
+                    .bg(app.theme.bg_primary)
+            };
+
+            ListItem::new(Line::styled(text, style))
+        })
+        .collect();
+
+    let border = if focused { app.theme.accent } else { app.theme.fg_secondary };
+    let list = List::new(items)
+        .block(Block::default()
+            .title("Feeds")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .style(Style::default().bg(app.theme.bg_primary)));
+
+    let mut state = ListState::default();
+    state.select(Some(app.feed_menu_selected));
+
+    f.render_stateful_widget(list, area, &mut state);
+}
+
 fn render_help_menu(f: &mut Frame, app: &App) {
-    // Create centered popup
+    let heading = Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD);
+    let text = Style::default().fg(app.theme.fg_primary);
+
+    // Help content is generated from the keymap, one section per group of actions
+    let mut sections: Vec<(&str, Vec<Line>)> = Vec::new();
+    for mode in KeyMode::ALL {
+        for binding in app.keymap.bindings(mode) {
+            if binding.keys.0.is_empty() {
+                continue;
+            }
+            let keys: Vec<String> = binding.keys.0.iter().map(|keys| keys.to_string()).collect();
+            let line = match binding.action {
+                // One line for all the tab numbers
+                Action::GoToTab(1) => {
+                    let last = app.keymap.hint(mode, Action::GoToTab(9)).unwrap_or_default();
+                    Line::from(Span::styled(format!("  {:<14} {}", format!("{}…{}", keys[0], last), "Go to tab 1-9"), text))
+                }
+                Action::GoToTab(_) => continue,
+                action => Line::from(Span::styled(format!("  {:<14} {}", keys.join(" / "), action.description()), text)),
+            };
+            match sections.last_mut() {
+                Some((title, lines)) if *title == binding.section => lines.push(line),
+                _ => sections.push((binding.section, vec![line])),
+            }
+        }
+    }
+
+    // Counts aren't bindings, but work with any move
+    let scroll_down = app.keymap.hint(KeyMode::Normal, Action::ScrollDown).unwrap_or_default();
+    let bottom = app.keymap.hint(KeyMode::Normal, Action::ScrollBottom).unwrap_or_default();
+    sections.push(("Counts", vec![
+        Line::from(Span::styled(format!("  {:<14} {}", format!("5{}", scroll_down), "Repeat a move five times"), text)),
+        Line::from(Span::styled(format!("  {:<14} {}", format!("12{}", bottom), "Jump to story 12"), text)),
+        Line::from(Span::styled(format!("  {:<14} {}", "12 Enter", "Jump to story 12"), text)),
+    ]));
+
+    // Two columns when there's room, otherwise one long column
     let area = f.area();
-    let popup_width = 70.min(area.width - 4);
-    let popup_height = 22.min(area.height - 4);  // Enough for all help items + padding
+    let total_lines: usize = sections.iter().map(|(_, lines)| lines.len() + 2).sum();
+    let columns = if area.width >= 110 { 2 } else { 1 };
+    let mut column_text: Vec<Vec<Line>> = vec![Vec::new(); columns];
+    let mut column = 0;
+    for (title, lines) in sections {
+        if column + 1 < columns && column_text[column].len() >= total_lines / columns {
+            column += 1;
+        }
+        if !column_text[column].is_empty() {
+            column_text[column].push(Line::from(""));
+        }
+        column_text[column].push(Line::from(Span::styled(title, heading)));
+        column_text[column].extend(lines);
+    }
+    let content_height = column_text.iter().map(Vec::len).max().unwrap_or(0) as u16;
+
+    // Create centered popup
+    let popup_width = (55 * columns as u16 + 2).min(area.width.saturating_sub(4));
+    let popup_height = (content_height + 2).min(area.height.saturating_sub(4));
 
     let popup_area = Rect {
         x: (area.width.saturating_sub(popup_width)) / 2,
@@ -504,109 +924,179 @@ fn render_help_menu(f: &mut Frame, app: &App) {
     // Clear the area
     f.render_widget(Clear, popup_area);
 
-    // Create help content
-    let help_text = vec![
-        Line::from(Span::styled(
-            "Navigation",
-            Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)
-        )),
-        Line::from(Span::styled(
-            "  j / ↓          Scroll down",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  k / ↑          Scroll up",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  G              Jump to bottom",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  l              Jump to top",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Actions",
-            Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)
-        )),
-        Line::from(Span::styled(
-            "  o              Open article in browser",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  O              Open in new tab",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  Space          Jump to ticker article",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  r              Refresh news feed",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Views",
-            Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)
-        )),
-        Line::from(Span::styled(
-            "  Tab            Toggle preview pane",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  a / Enter      Open article view",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  f              Open feed selector",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Settings",
-            Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)
-        )),
-        Line::from(Span::styled(
-            "  s              Cycle sort order",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  t              Toggle date format",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  T              Cycle theme (light/dark)",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  p              Cycle image protocol",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(""),
-        Line::from(Span::styled(
-            "Other",
-            Style::default().fg(app.theme.accent).add_modifier(Modifier::BOLD)
-        )),
-        Line::from(Span::styled(
-            "  q / Esc        Quit",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-        Line::from(Span::styled(
-            "  ?              Toggle this help menu",
-            Style::default().fg(app.theme.fg_primary)
-        )),
-    ];
+    let close_keys: Vec<String> = app.keymap.keys(KeyMode::Help, Action::Close).iter().map(|keys| keys.to_string()).collect();
+    let block = Block::default()
+        .title(format!("Help ({} to close)", close_keys.join(" or ")))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.bg_primary));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
 
-    let help_paragraph = Paragraph::new(help_text)
-        .block(Block::default()
-            .title("Help (? or Esc to close)")
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(app.theme.accent))
-            .style(Style::default().bg(app.theme.bg_primary)))
-        .alignment(Alignment::Left);
+    let column_areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+        .split(inner);
+
+    for (lines, column_area) in column_text.into_iter().zip(column_areas.iter()) {
+        let help_paragraph = Paragraph::new(lines).alignment(Alignment::Left);
+        f.render_widget(help_paragraph, *column_area);
+    }
+}
 
-    f.render_widget(help_paragraph, popup_area);
+fn render_palette(f: &mut Frame, app: &App, palette: &Palette) {
+    // Near the top, like an editor's palette, so the list below stays in view
+    let area = f.area();
+    let width = 70.min(area.width.saturating_sub(4));
+    let height = 16.min(area.height.saturating_sub(4));
+    let popup_area = Rect {
+        x: (area.width.saturating_sub(width)) / 2,
+        y: area.height.saturating_sub(height).min(3),
+        width,
+        height,
+    };
+    f.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .title(format!("Command palette ({} matches)", palette.match_count()))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(app.theme.accent))
+        .style(Style::default().bg(app.theme.bg_primary));
+    let inner = block.inner(popup_area);
+    f.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    let prompt = Paragraph::new(format!("> {}_", palette.query))
+        .style(Style::default().fg(app.theme.fg_primary).add_modifier(Modifier::BOLD));
+    f.render_widget(prompt, chunks[0]);
+
+    let width = chunks[1].width as usize;
+    let items: Vec<ListItem> = palette.matches()
+        .enumerate()
+        .map(|(i, item)| {
+            // Label on the left, key or kind on the right
+            let hint_width = text_layout::width(&item.hint);
+            let label = text_layout::truncate(&item.label, width.saturating_sub(hint_width + 3));
+            let padding = width.saturating_sub(text_layout::width(&label) + hint_width + 1);
+            let text = format!(" {}{}{}", label, " ".repeat(padding), item.hint);
+
+            let style = if i == palette.selected {
+                app.theme.selection_style()
+            } else {
+                Style::default().fg(app.theme.fg_primary).bg(app.theme.bg_primary)
+            };
+            ListItem::new(Line::styled(text, style))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(palette.selected));
+    f.render_stateful_widget(List::new(items), chunks[1], &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::Theme;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn app_with_titles(titles: &[&str]) -> App {
+        let mut app = App::new(Theme::light());
+        app.humanize_dates = false;
+        app.update_stories(titles.iter().enumerate().map(|(i, title)| NewsStory {
+            title: title.to_string(),
+            description: String::new(),
+            link: format!("https://www.bbc.co.uk/news/{}", i),
+            pub_date: String::from("2025-01-01 12:00"),
+            category: String::from("News"),
+            image_url: None,
+        }).collect());
+        app
+    }
+
+    fn draw(width: u16, height: u16, draw: impl FnOnce(&mut Frame)) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal.draw(draw).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    // Rows as they look on screen; the cell after a wide character is covered by it
+    fn snapshot(buffer: &Buffer) -> Vec<String> {
+        let area = buffer.area;
+        (area.top()..area.bottom()).map(|y| {
+            let mut row = String::new();
+            let mut x = area.left();
+            while x < area.right() {
+                let symbol = buffer[(x, y)].symbol();
+                row.push_str(symbol);
+                x += text_layout::width(symbol).max(1) as u16;
+            }
+            row
+        }).collect()
+    }
+
+    #[test]
+    fn test_story_list_fits_multibyte_titles() {
+        let app = app_with_titles(&[
+            "“Café” owner speaks",
+            "日本で大きな地震が発生しました",
+            "Zoë’s 🎉 party 👩‍👩‍👧 family",
+        ]);
+        let buffer = draw(24, 6, |f| render_stories(f, f.area(), &app));
+        assert_eq!(snapshot(&buffer), vec![
+            "1. “Café” owner speaks  ",
+            "   Last updated: 2025...",
+            "2. 日本で大きな地震が...",
+            "   Last updated: 2025...",
+            "3. Zoë’s 🎉 party 👩‍👩‍👧... ",
+            "   Last updated: 2025...",
+        ]);
+
+        // The selected row's background reaches the last column
+        let last = buffer[(23, 0)].style();
+        assert_eq!(last.bg, Some(app.theme.selection_bg));
+    }
+
+    #[test]
+    fn test_page_size_follows_story_list() {
+        let mut app = app_with_titles(&[]);
+        let wide = Size::new(120, 24);
+        assert_eq!(list_height(&app, wide), 20);
+        app.show_preview = true;
+        assert_eq!(list_height(&app, wide), 20);
+        // Narrower than `stack_below`, the preview moves under the list
+        assert_eq!(list_height(&app, Size::new(80, 24)), 16);
+        app.layout.stack_below = 0;
+        assert_eq!(list_height(&app, Size::new(80, 24)), 20);
+        app.layout.preview_position = PreviewPosition::Bottom;
+        assert_eq!(list_height(&app, wide), 16);
+    }
+
+    #[test]
+    fn test_header_fits_wide_feed_names() {
+        let mut app = app_with_titles(&["Story"]);
+        app.current_feed = crate::feeds::Feed::new("日本のニュース速報", "https://feeds.bbci.co.uk/japanese/rss.xml");
+        app.open_tab(crate::feeds::Feed::new("🌍 World", "https://feeds.bbci.co.uk/news/world/rss.xml"));
+        app.switch_tab(0);
+        let buffer = draw(38, 3, |f| render_header(f, f.area(), &app));
+        // The feed name is cut before a character that would straddle the edge
+        assert_eq!(snapshot(&buffer), vec![
+            "              BBC | NEWS              ",
+            "Last updated: 2025-01-01 12:00 | 日...",
+            " 1 日本のニュース速報 (1)  2 🌍 World ",
+        ]);
+    }
+
+    #[test]
+    fn test_ticker_keeps_its_position_in_view() {
+        let mut app = app_with_titles(&[]);
+        app.ticker_stories = app_with_titles(&["中国经济增长放缓的原因"]).stories;
+        let buffer = draw(34, 1, |f| render_footer(f, f.area(), &app));
+        let row = &snapshot(&buffer)[0];
+        assert!(row.starts_with("[LATEST] 中国经济... (1/1)"), "{}", row);
+    }
 }